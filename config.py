@@ -1,19 +1,151 @@
+import copy
 import json
 import os
+import tomllib
+
+from logging_utils import log_info, log_error
 
 CONFIG_FILE = 'config.json'
+TOML_FILE = 'gema.toml'
+
+# Pfade, die für Installationen von außen gesetzt werden können: Einstellung -> (Umgebungsvariable, Standard)
+PATH_SETTINGS = {
+    "labelcodes_file": ("GEMA_LABELCODES", "Labelcodes.txt"),
+    "default_output_dir": ("GEMA_EXPORT_DIR", "."),
+}
+
+DEFAULT_CONFIG = {
+    "labelcodes_file": "Labelcodes.txt",
+    "default_output_dir": ".",
+    "csv_columns": ["Index", "Titel", "Künstler", "Labelcode", "Dauer"],
+    "index_token_count": 0,
+    "label_code_targets": {},
+    "ignore_index_leading_zeros": False,
+    "rename_template": "{index}_{titel}_{kuenstler}.{ext}",
+    "entry_delimiter": "",
+    "merge_durations": False,
+    "max_file_count": 5000,
+    "default_duration": "",
+    "separators": "_",
+    "musicbrainz_enabled": False,
+    "normalize_case": False,
+    "autosave_interval": 60,
+    "filename_patterns": [],
+    "csv_quoting": "minimal",
+    "path_display": "absolut",
+    "path_display_base": "",
+    "submission_id": {"prefix": "SUB-", "width": 4, "start": 1},
+    "decimal_separator": ",",
+    "header_line": "auto",
+    "file_log_level": "aus",
+    "compact_view": False,
+    "bracket_metadata": False,
+    "recent_paths": [],
+    "check_index_sequence": False,
+    "csv_headers": {},
+    "fixed_widths": {},
+    "auto_parse": True,
+    "strip_chars": "",
+    "field_replacements": [],
+    "extra_labelcodes_files": [],
+    "filename_duration_patterns": [],
+    "index_numbers": "unveraendert",
+    "index_number_width": 3,
+    "tsv_quoting": "keine",
+    "text_only_tracks": "behalten",
+    "artist_separator": " ",
+    "gema_portal": {"separator": "\t", "header": False},
+    "confirm_export_transforms": False,
+    "segment_targets": [],
+    "original_case_columns": [],
+    "strict_mode": False,
+    "required_fields": ["Index", "Titel", "Künstler", "Labelcode", "Dauer"]
+}
+
+# Beim Zurücksetzen bleiben Sprache, zuletzt benutzte Pfade sowie Labelcode-Dateien und Export-Ordner erhalten;
+# sie beschreiben die Umgebung und sind keine Einstellungen
+RESET_KEEP_KEYS = ["language", "recent_paths", "labelcodes_file", "extra_labelcodes_files", "default_output_dir"]
+
+def default_settings(config):
+    settings = copy.deepcopy(DEFAULT_CONFIG)
+    settings.update({key: config[key] for key in RESET_KEEP_KEYS if key in config})
+    return settings
 
 def load_config():
     if not os.path.exists(CONFIG_FILE):
         # Standard-Config erstellen
-        default_config = {
-            "labelcodes_file": "Labelcodes.txt",
-            "default_output_dir": ".",
-            "csv_columns": ["Index", "Titel", "Künstler", "Labelcode", "Dauer"]
-        }
         with open(CONFIG_FILE, 'w', encoding='utf-8') as f:
-            json.dump(default_config, f, indent=2)
-        return default_config
+            json.dump(DEFAULT_CONFIG, f, indent=2)
+        return copy.deepcopy(DEFAULT_CONFIG)
     else:
         with open(CONFIG_FILE, 'r', encoding='utf-8') as f:
             return json.load(f)
+
+def load_toml_config(path=TOML_FILE):
+    if not os.path.exists(path):
+        return {}
+    try:
+        with open(path, 'rb') as f:
+            return tomllib.load(f)
+    except (OSError, tomllib.TOMLDecodeError) as e:
+        log_error(f"{path} konnte nicht gelesen werden: {e}")
+        return {}
+
+def resolve_paths(config, cli_values=None, environ=None, toml_config=None, report=None):
+    # Reihenfolge: Kommandozeile > Umgebungsvariable > gema.toml > config.json > Standard.
+    # config.json selbst wird nicht verändert, damit Überschreibungen nicht gespeichert werden.
+    # report(Meldung) gibt die Quelle zusätzlich aus, z.B. auf stderr im Kommandozeilenbetrieb
+    cli_values = cli_values or {}
+    environ = os.environ if environ is None else environ
+    toml_config = load_toml_config() if toml_config is None else toml_config
+    resolved = {}
+    for key, (env_name, default) in PATH_SETTINGS.items():
+        candidates = [(cli_values.get(key), "Kommandozeile"), (environ.get(env_name), env_name),
+                      (toml_config.get(key), TOML_FILE), (config.get(key), CONFIG_FILE)]
+        value, source = next(((v, s) for v, s in candidates if v), (default, "Standard"))
+        message = f"{key} = {value} (Quelle: {source})"
+        log_info(message)
+        if report:
+            report(message)
+        resolved[key] = value
+    return resolved
+
+def save_config(config):
+    with open(CONFIG_FILE, 'w', encoding='utf-8') as f:
+        json.dump(config, f, indent=2, ensure_ascii=False)
+
+DRAFT_FILE = 'entwurf.json'
+EXPORT_SNAPSHOT_FILE = 'letzter_export.json'
+
+def write_json_file(path, data):
+    # Erst in eine Temp-Datei schreiben, damit ein Absturz beim Speichern die alte Datei nicht zerstört
+    tmp_file = path + '.tmp'
+    with open(tmp_file, 'w', encoding='utf-8') as f:
+        json.dump(data, f, indent=2, ensure_ascii=False)
+    os.replace(tmp_file, path)
+
+def read_json_file(path):
+    if not os.path.exists(path):
+        return None
+    try:
+        with open(path, 'r', encoding='utf-8') as f:
+            return json.load(f)
+    except (OSError, ValueError):
+        return None
+
+def save_draft(draft):
+    write_json_file(DRAFT_FILE, draft)
+
+def load_draft():
+    return read_json_file(DRAFT_FILE)
+
+def remove_draft():
+    if os.path.exists(DRAFT_FILE):
+        os.remove(DRAFT_FILE)
+
+def save_export_snapshot(snapshot):
+    # Eingabedatei -> exportierte Zeilen; Dateien aus früheren Läufen bleiben erhalten
+    write_json_file(EXPORT_SNAPSHOT_FILE, dict(load_export_snapshot(), **snapshot))
+
+def load_export_snapshot():
+    return read_json_file(EXPORT_SNAPSHOT_FILE) or {}
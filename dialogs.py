@@ -0,0 +1,561 @@
+import json
+import os
+import re
+from dataclasses import replace
+
+from PyQt5.QtWidgets import (QDialog, QVBoxLayout, QHBoxLayout, QPushButton, QTableWidget,
+                             QTableWidgetItem, QHeaderView, QMessageBox, QFormLayout, QLineEdit,
+                             QComboBox, QLabel, QCheckBox, QTabWidget, QWidget, QSpinBox, QPlainTextEdit)
+
+from PyQt5.QtCore import Qt
+from PyQt5.QtGui import QBrush, QColor
+
+from processing import (read_labelcode_entries, validate_labelcode_entries, save_labelcodes, parse_duration,
+                        parse_track_info, rule_replacement, display_text, RULE_FIELDS, bulk_replace,
+                        BULK_REPLACE_FIELDS, CSV_QUOTING)
+from config import DEFAULT_CONFIG
+from logging_utils import LOG_LEVELS
+from i18n import Language, tr
+
+class LabelcodesDialog(QDialog):
+    def __init__(self, labelcodes_file, language, parent=None):
+        super().__init__(parent)
+        self.labelcodes_file = labelcodes_file
+        self.language = language
+        self.setWindowTitle(tr("labelcodes_button", language))
+        
+        self.table = QTableWidget(0, 2, self)
+        self.table.setHorizontalHeaderLabels(tr("labelcodes_headers", language))
+        self.table.horizontalHeader().setSectionResizeMode(QHeaderView.Stretch)
+        for label, code in read_labelcode_entries(labelcodes_file):
+            self.add_row(label, code)
+        
+        add_button = QPushButton(tr("labelcodes_add", language), self)
+        add_button.clicked.connect(lambda: self.add_row("", ""))
+        delete_button = QPushButton(tr("labelcodes_delete", language), self)
+        delete_button.clicked.connect(self.delete_selected_rows)
+        save_button = QPushButton(tr("labelcodes_save", language), self)
+        save_button.clicked.connect(self.save)
+        cancel_button = QPushButton(tr("cancel", language), self)
+        cancel_button.clicked.connect(self.reject)
+        
+        button_layout = QHBoxLayout()
+        button_layout.addWidget(add_button)
+        button_layout.addWidget(delete_button)
+        button_layout.addStretch()
+        button_layout.addWidget(cancel_button)
+        button_layout.addWidget(save_button)
+        
+        layout = QVBoxLayout()
+        layout.addWidget(self.table)
+        layout.addLayout(button_layout)
+        self.setLayout(layout)
+        self.resize(400, 500)
+    
+    def add_row(self, label, code):
+        row = self.table.rowCount()
+        self.table.insertRow(row)
+        self.table.setItem(row, 0, QTableWidgetItem(label))
+        self.table.setItem(row, 1, QTableWidgetItem(code))
+    
+    def delete_selected_rows(self):
+        rows = sorted({index.row() for index in self.table.selectedIndexes()}, reverse=True)
+        for row in rows:
+            self.table.removeRow(row)
+    
+    def entries(self):
+        entries = []
+        for row in range(self.table.rowCount()):
+            cells = [self.table.item(row, column) for column in range(2)]
+            entries.append(tuple(cell.text() if cell else "" for cell in cells))
+        return entries
+    
+    def save(self):
+        entries = self.entries()
+        problems = validate_labelcode_entries(entries, self.language)
+        if problems:
+            QMessageBox.warning(self, tr("labelcodes_button", self.language),
+                                tr("labelcodes_invalid", self.language) + "\n" + "\n".join(problems))
+            return
+        save_labelcodes(self.labelcodes_file, entries)
+        self.accept()
+
+class TrackEditDialog(QDialog):
+    # Korrektur von Titel, Künstler, Labelcode und Dauer einer Zeile, wird als Override gespeichert
+    def __init__(self, row, language, parent=None):
+        super().__init__(parent)
+        self.language = language
+        self.setWindowTitle(tr("track_edit_title", language, filename=row["filename"]))
+        
+        self.title_edit = QLineEdit(row["title"], self)
+        self.artist_edit = QLineEdit(row["artist"], self)
+        self.label_code_edit = QLineEdit(row["label_code"], self)
+        # Platzhalter-Dauern tragen ein "*", das gehört nicht zum Wert
+        self.original_duration = (row["duration"] or "").rstrip("*")
+        self.duration_edit = QLineEdit(self.original_duration, self)
+        
+        form = QFormLayout()
+        form.addRow(tr("track_edit_field_title", language), self.title_edit)
+        form.addRow(tr("track_edit_field_artist", language), self.artist_edit)
+        form.addRow(tr("track_edit_field_label_code", language), self.label_code_edit)
+        form.addRow(tr("track_edit_field_duration", language), self.duration_edit)
+        
+        self.ok_button = QPushButton(tr("labelcodes_save", language), self)
+        self.ok_button.setDefault(True)
+        self.ok_button.clicked.connect(self.accept)
+        cancel_button = QPushButton(tr("cancel", language), self)
+        cancel_button.clicked.connect(self.reject)
+        
+        button_layout = QHBoxLayout()
+        button_layout.addStretch()
+        button_layout.addWidget(cancel_button)
+        button_layout.addWidget(self.ok_button)
+        
+        layout = QVBoxLayout()
+        layout.addLayout(form)
+        layout.addLayout(button_layout)
+        self.setLayout(layout)
+        self.resize(400, 0)
+        
+        # Schon beim Tippen prüfen, damit kein Fehler von Hand in den Export gelangt
+        for edit in [self.title_edit, self.duration_edit]:
+            edit.textChanged.connect(self.validate)
+        self.validate()
+    
+    def problems(self):
+        # Feld -> Grund; leer, wenn alles gespeichert werden kann
+        problems = {}
+        if not self.title_edit.text().strip():
+            problems[self.title_edit] = tr("track_edit_required", self.language)
+        duration = self.duration_edit.text().strip()
+        # Leer bleiben darf die Dauer nur, wenn die Zeile schon vorher keine gültige hatte
+        if parse_duration(duration) is None and (duration or self.original_duration):
+            problems[self.duration_edit] = tr("track_edit_invalid_duration", self.language)
+        return problems
+    
+    def validate(self):
+        problems = self.problems()
+        for edit in [self.title_edit, self.duration_edit]:
+            edit.setStyleSheet("background: #ffd0d0;" if edit in problems else "")
+            edit.setToolTip(problems.get(edit, ""))
+        self.ok_button.setEnabled(not problems)
+    
+    def values(self):
+        values = {"title": self.title_edit.text().strip(),
+                  "artist": self.artist_edit.text().strip(),
+                  "label_code": self.label_code_edit.text().strip()}
+        duration = self.duration_edit.text().strip()
+        if duration != self.original_duration:
+            # Nur eine geänderte Dauer übernehmen, sonst gälte sie für alle Zeilen mit diesem Dateinamen
+            values["duration"] = duration
+        return values
+
+class ColumnHeadersDialog(QDialog):
+    # Eigene Überschriften für die CSV-Spalten; leer = Spaltenname
+    def __init__(self, csv_columns, csv_headers, language, parent=None):
+        super().__init__(parent)
+        self.csv_columns = csv_columns
+        self.setWindowTitle(tr("headers_button", language))
+        
+        self.table = QTableWidget(len(csv_columns), 2, self)
+        self.table.setHorizontalHeaderLabels(tr("headers_table_headers", language))
+        self.table.horizontalHeader().setSectionResizeMode(QHeaderView.Stretch)
+        for row, column in enumerate(csv_columns):
+            name_item = QTableWidgetItem(column)
+            name_item.setFlags(name_item.flags() & ~Qt.ItemIsEditable)
+            self.table.setItem(row, 0, name_item)
+            self.table.setItem(row, 1, QTableWidgetItem(csv_headers.get(column, "")))
+        
+        save_button = QPushButton(tr("labelcodes_save", language), self)
+        save_button.clicked.connect(self.accept)
+        cancel_button = QPushButton(tr("cancel", language), self)
+        cancel_button.clicked.connect(self.reject)
+        
+        button_layout = QHBoxLayout()
+        button_layout.addStretch()
+        button_layout.addWidget(cancel_button)
+        button_layout.addWidget(save_button)
+        
+        layout = QVBoxLayout()
+        layout.addWidget(self.table)
+        layout.addLayout(button_layout)
+        self.setLayout(layout)
+        self.resize(400, 300)
+    
+    def headers(self):
+        # Nicht gestrippt: manche Vorlagen verlangen die Überschrift Byte für Byte
+        headers = {}
+        for row, column in enumerate(self.csv_columns):
+            item = self.table.item(row, 1)
+            if item and item.text():
+                headers[column] = item.text()
+        return headers
+
+class RulesDialog(QDialog):
+    # Ersetzungsregeln nach dem Zerlegen, in Tabellenreihenfolge; mit einem Beispiel-Dateinamen testbar
+    def __init__(self, replacements, options, language, parent=None):
+        super().__init__(parent)
+        self.options = options
+        self.language = language
+        self.setWindowTitle(tr("rules_button", language))
+        
+        self.table = QTableWidget(0, 4, self)
+        self.table.setHorizontalHeaderLabels(tr("rules_headers", language))
+        self.table.horizontalHeader().setSectionResizeMode(QHeaderView.Stretch)
+        for entry in replacements:
+            if isinstance(entry, dict):
+                self.add_row(entry.get("feld", "alle"), entry.get("suchen", ""), entry.get("ersetzen", ""),
+                             entry.get("regex", False))
+            elif isinstance(entry, (list, tuple)) and len(entry) == 2:
+                # Ältere Einträge [Muster, Ersatz] sind reguläre Ausdrücke für alle Felder
+                self.add_row("alle", str(entry[0]), str(entry[1]), True)
+        
+        add_button = QPushButton(tr("labelcodes_add", language), self)
+        add_button.clicked.connect(lambda: self.add_row("alle", "", "", False))
+        delete_button = QPushButton(tr("labelcodes_delete", language), self)
+        delete_button.clicked.connect(self.delete_selected_rows)
+        
+        self.sample_edit = QLineEdit(self)
+        self.sample_edit.setPlaceholderText(tr("rules_sample_placeholder", language))
+        self.sample_edit.returnPressed.connect(self.test_rules)
+        test_button = QPushButton(tr("rules_test", language), self)
+        test_button.clicked.connect(self.test_rules)
+        self.result_label = QLabel(self)
+        self.result_label.setWordWrap(True)
+        
+        save_button = QPushButton(tr("labelcodes_save", language), self)
+        save_button.clicked.connect(self.save)
+        cancel_button = QPushButton(tr("cancel", language), self)
+        cancel_button.clicked.connect(self.reject)
+        
+        sample_layout = QHBoxLayout()
+        sample_layout.addWidget(self.sample_edit)
+        sample_layout.addWidget(test_button)
+        
+        button_layout = QHBoxLayout()
+        button_layout.addWidget(add_button)
+        button_layout.addWidget(delete_button)
+        button_layout.addStretch()
+        button_layout.addWidget(cancel_button)
+        button_layout.addWidget(save_button)
+        
+        layout = QVBoxLayout()
+        layout.addWidget(self.table)
+        layout.addLayout(sample_layout)
+        layout.addWidget(self.result_label)
+        layout.addLayout(button_layout)
+        self.setLayout(layout)
+        self.resize(600, 400)
+    
+    def add_row(self, field_name, search, replacement, regex):
+        row = self.table.rowCount()
+        self.table.insertRow(row)
+        field_combo = QComboBox(self.table)
+        for key in RULE_FIELDS:
+            field_combo.addItem(tr("rules_field_" + key, self.language), key)
+        field_combo.setCurrentIndex(list(RULE_FIELDS).index(field_name) if field_name in RULE_FIELDS else 0)
+        self.table.setCellWidget(row, 0, field_combo)
+        self.table.setItem(row, 1, QTableWidgetItem(search))
+        self.table.setItem(row, 2, QTableWidgetItem(replacement))
+        regex_item = QTableWidgetItem()
+        regex_item.setFlags(regex_item.flags() | Qt.ItemIsUserCheckable)
+        regex_item.setCheckState(Qt.Checked if regex else Qt.Unchecked)
+        self.table.setItem(row, 3, regex_item)
+    
+    def delete_selected_rows(self):
+        rows = sorted({index.row() for index in self.table.selectedIndexes()}, reverse=True)
+        for row in rows:
+            self.table.removeRow(row)
+    
+    def rules(self):
+        rules = []
+        for row in range(self.table.rowCount()):
+            search, replacement = (self.table.item(row, column) for column in (1, 2))
+            regex = self.table.item(row, 3)
+            rules.append({"feld": self.table.cellWidget(row, 0).currentData(),
+                          "suchen": search.text() if search else "",
+                          "ersetzen": replacement.text() if replacement else "",
+                          "regex": bool(regex and regex.checkState() == Qt.Checked)})
+        return rules
+    
+    def checked_rules(self):
+        # Liefert (gültige Ersetzungen, Meldungen zu ungültigen Zeilen)
+        valid, problems = [], []
+        for row, rule in enumerate(self.rules(), start=1):
+            try:
+                pattern, replacement, field_name = rule_replacement(rule)
+                re.compile(pattern)
+            except (ValueError, re.error) as e:
+                problems.append(tr("rules_invalid", self.language, row=row, error=e))
+                continue
+            valid.append((pattern, replacement, field_name))
+        return valid, problems
+    
+    def test_rules(self):
+        valid, problems = self.checked_rules()
+        sample = self.sample_edit.text().strip()
+        lines = list(problems)
+        if sample:
+            options = replace(self.options, field_replacements=valid, overrides={})
+            track = parse_track_info(sample, {}, options)
+            lines.append(tr("rules_result", self.language, index=track.raw_index,
+                            title=display_text(track.title, options), artist=display_text(track.artist, options)))
+        self.result_label.setText("\n".join(lines))
+    
+    def save(self):
+        _, problems = self.checked_rules()
+        if problems:
+            QMessageBox.warning(self, tr("rules_button", self.language), "\n".join(problems))
+            return
+        self.accept()
+
+class FindReplaceDialog(QDialog):
+    # Suchen und Ersetzen in einem Feld aller Zeilen der Debug-Ansicht; die Vorschau folgt jeder Eingabe
+    def __init__(self, rows, language, parent=None):
+        super().__init__(parent)
+        self.rows = rows
+        self.language = language
+        self.setWindowTitle(tr("find_replace_button", language))
+        
+        self.field_combo = QComboBox(self)
+        for field_name, label in zip(BULK_REPLACE_FIELDS, tr("find_replace_fields", language)):
+            self.field_combo.addItem(label, field_name)
+        self.search_edit = QLineEdit(self)
+        self.replace_edit = QLineEdit(self)
+        self.case_checkbox = QCheckBox(tr("find_replace_case", language), self)
+        self.whole_word_checkbox = QCheckBox(tr("find_replace_whole_word", language), self)
+        
+        form = QFormLayout()
+        form.addRow(tr("find_replace_field", language), self.field_combo)
+        form.addRow(tr("find_replace_search", language), self.search_edit)
+        form.addRow(tr("find_replace_replace", language), self.replace_edit)
+        form.addRow(self.case_checkbox)
+        form.addRow(self.whole_word_checkbox)
+        
+        self.table = QTableWidget(0, 3, self)
+        self.table.setHorizontalHeaderLabels(tr("find_replace_headers", language))
+        self.table.horizontalHeader().setSectionResizeMode(QHeaderView.Stretch)
+        self.table.setEditTriggers(QTableWidget.NoEditTriggers)
+        self.preview_label = QLabel(self)
+        
+        self.apply_button = QPushButton(tr("find_replace_apply", language), self)
+        self.apply_button.clicked.connect(self.accept)
+        cancel_button = QPushButton(tr("cancel", language), self)
+        cancel_button.clicked.connect(self.reject)
+        
+        button_layout = QHBoxLayout()
+        button_layout.addWidget(self.preview_label)
+        button_layout.addStretch()
+        button_layout.addWidget(cancel_button)
+        button_layout.addWidget(self.apply_button)
+        
+        layout = QVBoxLayout()
+        layout.addLayout(form)
+        layout.addWidget(self.table)
+        layout.addLayout(button_layout)
+        self.setLayout(layout)
+        self.resize(600, 400)
+        
+        self.field_combo.currentIndexChanged.connect(self.update_preview)
+        for edit in [self.search_edit, self.replace_edit]:
+            edit.textChanged.connect(self.update_preview)
+        for checkbox in [self.case_checkbox, self.whole_word_checkbox]:
+            checkbox.toggled.connect(self.update_preview)
+        self.update_preview()
+    
+    def field_name(self):
+        return self.field_combo.currentData()
+    
+    def changes(self):
+        return bulk_replace(self.rows, self.field_name(), self.search_edit.text(), self.replace_edit.text(),
+                            self.case_checkbox.isChecked(), self.whole_word_checkbox.isChecked())
+    
+    def update_preview(self):
+        changes = self.changes()
+        self.table.setRowCount(len(changes))
+        for r, values in enumerate(changes):
+            for c, value in enumerate(values):
+                self.table.setItem(r, c, QTableWidgetItem(value))
+        self.preview_label.setText(tr("find_replace_preview", self.language, count=len(changes)))
+        self.apply_button.setEnabled(bool(changes))
+
+# Reiter des Einstellungsfensters mit ihren Config-Schlüsseln. Die Art des Felds folgt aus dem Standardwert:
+# Häkchen, Zahl, Liste (eine Zeile je Eintrag) oder Text; feste Auswahl aus SETTING_CHOICES
+SETTINGS_TABS = [
+    ("einlesen", ["entry_delimiter", "separators", "header_line", "index_token_count", "filename_patterns",
+                  "filename_duration_patterns", "strip_chars", "default_duration", "artist_separator",
+                  "bracket_metadata"]),
+    ("abgleich", ["ignore_index_leading_zeros", "merge_durations", "normalize_case", "text_only_tracks",
+                  "check_index_sequence", "original_case_columns", "label_code_targets", "segment_targets"]),
+    ("export", ["csv_columns", "csv_quoting", "tsv_quoting", "decimal_separator", "index_numbers",
+                "index_number_width", "confirm_export_transforms", "strict_mode", "required_fields",
+                "rename_template", "submission_id", "fixed_widths", "gema_portal"]),
+    ("allgemein", ["language", "file_log_level", "max_file_count", "autosave_interval", "auto_parse",
+                   "compact_view", "path_display", "path_display_base", "musicbrainz_enabled"]),
+]
+# Zahlenfelder mit engeren Grenzen
+SETTING_RANGES = {"index_token_count": (0, 5)}
+SETTING_CHOICES = {
+    "header_line": ["auto", "immer", "nie"],
+    "text_only_tracks": ["behalten", "markieren", "ausschliessen"],
+    "csv_quoting": list(CSV_QUOTING),
+    "tsv_quoting": list(CSV_QUOTING),
+    "index_numbers": ["unveraendert", "ohne_nullen", "auffuellen"],
+    "language": [language.value for language in Language],
+    "file_log_level": LOG_LEVELS,
+    "path_display": ["absolut", "name", "relativ"],
+}
+# Verschachtelte Einstellungen werden als JSON bearbeitet
+JSON_SETTINGS = ["label_code_targets", "segment_targets", "submission_id", "fixed_widths", "gema_portal"]
+# Listen mit regulären Ausdrücken, die vor dem Übernehmen geprüft werden
+REGEX_SETTINGS = ["filename_patterns", "filename_duration_patterns"]
+
+class SettingsDialog(QDialog):
+    # Alle Einstellungen an einem Ort, in Reitern; values() liefert die geänderte Config zum Speichern
+    def __init__(self, config, language, parent=None):
+        super().__init__(parent)
+        self.language = language
+        self.setWindowTitle(tr("settings_button", language))
+        
+        self.fields = {}
+        self.tabs = QTabWidget(self)
+        for tab, keys in SETTINGS_TABS:
+            page = QWidget(self.tabs)
+            form = QFormLayout(page)
+            for key in keys:
+                self.fields[key] = self.create_field(key, config.get(key, DEFAULT_CONFIG.get(key)), page)
+                form.addRow(tr("settings_" + key, language), self.fields[key])
+            self.tabs.addTab(page, tr("settings_tab_" + tab, language))
+        
+        apply_button = QPushButton(tr("settings_apply", language), self)
+        apply_button.setDefault(True)
+        apply_button.clicked.connect(self.save)
+        cancel_button = QPushButton(tr("cancel", language), self)
+        cancel_button.clicked.connect(self.reject)
+        
+        button_layout = QHBoxLayout()
+        button_layout.addStretch()
+        button_layout.addWidget(cancel_button)
+        button_layout.addWidget(apply_button)
+        
+        layout = QVBoxLayout()
+        layout.addWidget(self.tabs)
+        layout.addLayout(button_layout)
+        self.setLayout(layout)
+        self.resize(500, 450)
+    
+    def create_field(self, key, value, parent):
+        default = DEFAULT_CONFIG.get(key, "")  # "language" hat keinen Standardwert, siehe language_from_config
+        if key in SETTING_CHOICES:
+            widget = QComboBox(parent)
+            widget.addItems(SETTING_CHOICES[key])
+            widget.setCurrentIndex(SETTING_CHOICES[key].index(value) if value in SETTING_CHOICES[key] else 0)
+        elif key in JSON_SETTINGS:
+            widget = QPlainTextEdit(json.dumps(value, ensure_ascii=False, indent=2), parent)
+        elif isinstance(default, bool):
+            widget = QCheckBox(parent)
+            widget.setChecked(bool(value))
+        elif isinstance(default, int):
+            widget = QSpinBox(parent)
+            widget.setRange(*SETTING_RANGES.get(key, (0, 1000000)))
+            widget.setValue(int(value))
+        elif isinstance(default, list):
+            widget = QPlainTextEdit("\n".join(value), parent)
+        else:
+            widget = QLineEdit(value, parent)
+        return widget
+    
+    def values(self):
+        values = {}
+        for key, widget in self.fields.items():
+            default = DEFAULT_CONFIG.get(key, "")
+            if key in SETTING_CHOICES:
+                values[key] = widget.currentText()
+            elif key in JSON_SETTINGS:
+                values[key] = self.json_value(key)
+            elif isinstance(default, bool):
+                values[key] = widget.isChecked()
+            elif isinstance(default, int):
+                values[key] = widget.value()
+            elif isinstance(default, list):
+                values[key] = [line.strip() for line in widget.toPlainText().splitlines() if line.strip()]
+            else:
+                # Nicht gestrippt: Leerzeichen können Trennzeichen sein (z.B. artist_separator)
+                values[key] = widget.text()
+        return values
+    
+    def json_value(self, key):
+        # Leeres Feld: leerer Wert vom Typ des Standardwerts; ValueError bei ungültigem JSON oder falschem Typ
+        text = self.fields[key].toPlainText().strip()
+        default = DEFAULT_CONFIG[key]
+        value = json.loads(text) if text else type(default)()
+        if not isinstance(value, type(default)):
+            raise ValueError(tr("settings_json_type_" + type(default).__name__, self.language))
+        return value
+    
+    def problems(self):
+        problems = []
+        for key in JSON_SETTINGS:
+            try:
+                self.json_value(key)
+            except ValueError as e:
+                problems.append(tr("settings_invalid_json", self.language,
+                                   setting=tr("settings_" + key, self.language), error=e))
+        if problems:
+            return problems
+        values = self.values()
+        for key in REGEX_SETTINGS:
+            for pattern in values[key]:
+                try:
+                    re.compile(pattern)
+                except re.error as e:
+                    problems.append(tr("settings_invalid_regex", self.language,
+                                       setting=tr("settings_" + key, self.language), pattern=pattern, error=e))
+        if not values["csv_columns"]:
+            problems.append(tr("settings_no_columns", self.language))
+        return problems
+    
+    def save(self):
+        problems = self.problems()
+        if problems:
+            QMessageBox.warning(self, tr("settings_button", self.language), "\n".join(problems))
+            return
+        self.accept()
+
+class ExportDiffDialog(QDialog):
+    # Unterschiede zum letzten Export; entries: (Datei, Status, Index, [(Feld, alt, neu), ...])
+    COLORS = {"neu": "darkgreen", "geaendert": "darkorange", "entfernt": "red"}
+    
+    def __init__(self, entries, language, parent=None):
+        super().__init__(parent)
+        self.setWindowTitle(tr("compare_button", language))
+        headers = tr("debug_headers", language)
+        field_names = {"title": headers[3], "artist": headers[4], "label_code": headers[5], "duration": headers[6]}
+        
+        self.table = QTableWidget(len(entries), 4, self)
+        self.table.setEditTriggers(QTableWidget.NoEditTriggers)
+        self.table.setHorizontalHeaderLabels(tr("diff_headers", language))
+        self.table.horizontalHeader().setSectionResizeMode(QHeaderView.Interactive)
+        self.table.horizontalHeader().setStretchLastSection(True)
+        for row, (path, status, index, fields) in enumerate(entries):
+            changes = "; ".join(tr("diff_field", language, field=field_names[name], old=old, new=new)
+                                for name, old, new in fields)
+            for column, value in enumerate([tr("diff_status_" + status, language), os.path.basename(path),
+                                            index, changes]):
+                item = QTableWidgetItem(value)
+                item.setForeground(QBrush(QColor(self.COLORS[status])))
+                item.setToolTip(path if column == 1 else value)
+                self.table.setItem(row, column, item)
+        
+        close_button = QPushButton(tr("close", language), self)
+        close_button.clicked.connect(self.accept)
+        
+        button_layout = QHBoxLayout()
+        button_layout.addStretch()
+        button_layout.addWidget(close_button)
+        
+        layout = QVBoxLayout()
+        layout.addWidget(self.table)
+        layout.addLayout(button_layout)
+        self.setLayout(layout)
+        self.resize(700, 400)
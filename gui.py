@@ -1,106 +1,1103 @@
+import copy
+import os
+import re
 import sys
+import threading
 import traceback
+from datetime import datetime
 from PyQt5.QtWidgets import (QWidget, QLabel, QVBoxLayout, QPushButton, QListWidget,
-                             QFileDialog, QProgressBar, QHBoxLayout)
-from PyQt5.QtCore import Qt
+                             QFileDialog, QProgressBar, QHBoxLayout, QTableWidget,
+                             QTableWidgetItem, QCheckBox, QHeaderView, QMessageBox,
+                             QLineEdit, QListWidgetItem, QComboBox, QShortcut, QAbstractItemView, QMenu,
+                             QApplication, QStatusBar)
+from PyQt5.QtCore import Qt, QThread, QTimer, pyqtSignal
+from PyQt5.QtGui import QBrush, QColor, QKeySequence
 
-from config import load_config
-from processing import load_labelcodes, list_txt_files_in_dir, process_single_file
-from logging_utils import log_error
+from config import (load_config, resolve_paths, save_config, save_draft, load_draft, remove_draft,
+                    save_export_snapshot, load_export_snapshot, default_settings)
+from processing import (merge_labelcodes, process_single_file, debug_parse_file, parse_text_file,
+                        label_code_aggregate, write_label_aggregate, snapshot_rows, compare_snapshots,
+                        ParseOptions, check_input_file, expand_pattern, plan_renames, apply_renames,
+                        export_errors, error_severity, ensure_extension, canonical_path, parse_duration,
+                        describe_text_file, scan_folder, track_rows, portal_text,
+                        export_transforms, TRANSFORM_KINDS, valid_replacements, write_template,
+                        check_segment_targets, INPUT_EXTENSIONS, ExportCancelled, strict_problems,
+                        duration_seconds, format_seconds)
+from logging_utils import log_error, log_info, configure_file_logging
+from dialogs import (LabelcodesDialog, TrackEditDialog, ColumnHeadersDialog, ExportDiffDialog, RulesDialog,
+                     FindReplaceDialog, SettingsDialog)
+from musicbrainz import lookup_recording
+from i18n import Language, tr, language_from_config
+
+ERROR_FILTERS = ["alle", "fehler", "warnung"]
+SEVERITY_COLORS = {"fehler": "red", "warnung": "darkorange"}
+TOAST_DURATION = 3000  # Millisekunden je Hinweis
+
+class LookupWorker(QThread):
+    # Führt die MusicBrainz-Abfrage außerhalb des GUI-Threads aus
+    lookup_finished = pyqtSignal(object, object)
+
+    def __init__(self, row, parent=None):
+        super().__init__(parent)
+        self.row = row
+
+    def run(self):
+        try:
+            result = lookup_recording(self.row["title"], self.row["artist"])
+        except Exception as e:
+            result = e
+        self.lookup_finished.emit(self.row, result)
+
+class ExportWorker(QThread):
+    # Schreibt die Ausgaben außerhalb des GUI-Threads; abbrechen zwischen zwei Zeilen mit cancel()
+    file_progress = pyqtSignal(int, int, int)  # Nummer der Datei, geschriebene Zeilen, Zeilen gesamt
+    file_done = pyqtSignal(int, str, str, object)  # Nummer der Datei, Pfad, Zusammenfassung, Fehler
+    export_finished = pyqtSignal(object, object)  # Stand des Exports, None / "abgebrochen" / Ausnahme
+
+    def __init__(self, file_paths, settings, parent=None):
+        super().__init__(parent)
+        self.file_paths = file_paths
+        self.settings = settings
+        self.cancel_event = threading.Event()
+
+    def cancel(self):
+        self.cancel_event.set()
+
+    def run(self):
+        snapshot = {}
+        outcome = None
+        try:
+            for i, input_file in enumerate(self.file_paths, start=1):
+                if self.cancel_event.is_set():
+                    raise ExportCancelled()
+                summary, errors = process_single_file(
+                    input_file, snapshot=snapshot, cancelled=self.cancel_event.is_set,
+                    progress=lambda written, total, i=i: self.file_progress.emit(i, written, total), **self.settings)
+                self.file_done.emit(i, input_file, summary, errors)
+        except ExportCancelled:
+            outcome = "abgebrochen"
+        except Exception as e:
+            log_error("Exception: " + traceback.format_exc())
+            outcome = e
+        self.export_finished.emit(snapshot, outcome)
+
+class PrecheckWorker(QThread):
+    # Liest vor dem Export jede Datei einmal außerhalb des GUI-Threads; die Rückfragen (strenger Modus, Abschnitte,
+    # Änderungen) verwenden diese Ergebnisse, statt selbst neu zu parsen
+    # [(Pfad, Problem oder None, ParseResult oder None, Änderungen)] oder None nach Abbruch
+    checks_finished = pyqtSignal(object)
+
+    def __init__(self, file_paths, label_dict, options, language, with_transforms, parent=None):
+        super().__init__(parent)
+        self.file_paths = file_paths
+        self.label_dict = label_dict
+        self.options = options
+        self.language = language
+        self.with_transforms = with_transforms
+        self.cancel_event = threading.Event()
+
+    def cancel(self):
+        self.cancel_event.set()
+
+    def run(self):
+        checks = []
+        for input_file in self.file_paths:
+            if self.cancel_event.is_set():
+                break
+            problem = check_input_file(input_file, self.language)
+            result, transforms = None, []
+            if not problem:
+                try:
+                    result = parse_text_file(input_file, self.label_dict, self.options)
+                    if self.with_transforms:
+                        transforms = export_transforms(input_file, self.label_dict, self.options)
+                except Exception as e:
+                    log_error(f"Prüfung vor dem Export für {input_file} fehlgeschlagen: {e}")
+                    problem, result = tr("file_error", self.language, path=input_file, error=e), None
+            checks.append((input_file, problem, result, transforms))
+        self.checks_finished.emit(None if self.cancel_event.is_set() else checks)
 
 class DragDropWindow(QWidget):
-    def __init__(self):
+    def __init__(self, path_overrides=None):
         super().__init__()
-        self.setWindowTitle("Track Parser")
         self.setAcceptDrops(True)
         
         self.config = load_config()
-        self.output_dir = self.config.get("default_output_dir", ".")
-        self.labelcodes_file = self.config.get("labelcodes_file", "Labelcodes.txt")
+        # Protokolldatei im Datenordner (aus/Fehler/Info/Debug), vor resolve_paths, damit die Quelle der Pfade im Protokoll steht
+        configure_file_logging(self.config.get("file_log_level", "aus"))
+        self.language = language_from_config(self.config.get("language"))
+        self.path_overrides = path_overrides
+        paths = resolve_paths(self.config, path_overrides)
+        self.output_dir = paths["default_output_dir"]
+        self.labelcodes_file = paths["labelcodes_file"]
         self.csv_columns = self.config.get("csv_columns", ["Index", "Titel", "Künstler", "Labelcode", "Dauer"])
-        self.label_dict = load_labelcodes(self.labelcodes_file)
+        self.load_label_dict()
+        self.parse_options = ParseOptions.from_config(self.config)
         
         # Obere Button-Leiste
-        self.output_button = QPushButton("Ausgabeort wählen", self)
-        self.output_button.setToolTip("Wähle den Ordner für die CSV-Ausgabe.")
+        self.output_button = QPushButton(self)
         self.output_button.clicked.connect(self.choose_output_directory)
         
-        self.reload_button = QPushButton("Labelcodes neu laden", self)
-        self.reload_button.setToolTip("Lade die Labelcodes neu.")
+        self.reload_button = QPushButton(self)
         self.reload_button.clicked.connect(self.reload_labelcodes)
         
-        self.file_select_button = QPushButton("Datei auswählen", self)
-        self.file_select_button.setToolTip("Wähle .txt-Dateien aus.")
+        self.reassign_button = QPushButton(self)
+        self.reassign_button.clicked.connect(self.reassign_labelcodes)
+        
+        self.headers_button = QPushButton(self)
+        self.headers_button.clicked.connect(self.edit_column_headers)
+        
+        self.rules_button = QPushButton(self)
+        self.rules_button.clicked.connect(self.edit_rules)
+        
+        self.labelcodes_button = QPushButton(self)
+        self.labelcodes_button.clicked.connect(self.manage_labelcodes)
+        
+        self.add_labelcodes_button = QPushButton(self)
+        self.add_labelcodes_button.clicked.connect(self.add_labelcodes_file)
+        
+        self.file_select_button = QPushButton(self)
         self.file_select_button.clicked.connect(self.select_files)
         
+        self.language_button = QPushButton(self)
+        self.language_button.clicked.connect(self.toggle_language)
+        
+        self.reset_settings_button = QPushButton(self)
+        self.reset_settings_button.clicked.connect(self.reset_settings)
+        
+        self.settings_button = QPushButton(self)
+        self.settings_button.clicked.connect(self.edit_settings)
+        
+        self.paste_button = QPushButton(self)
+        self.paste_button.clicked.connect(self.paste_from_clipboard)
+        
+        self.recent_button = QPushButton(self)
+        self.recent_menu = QMenu(self)
+        self.recent_menu.aboutToShow.connect(self.fill_recent_menu)
+        self.recent_button.setMenu(self.recent_menu)
+        
         top_layout = QHBoxLayout()
         top_layout.addWidget(self.output_button)
         top_layout.addWidget(self.reload_button)
+        top_layout.addWidget(self.reassign_button)
+        top_layout.addWidget(self.labelcodes_button)
+        top_layout.addWidget(self.add_labelcodes_button)
+        top_layout.addWidget(self.headers_button)
+        top_layout.addWidget(self.rules_button)
         top_layout.addWidget(self.file_select_button)
+        top_layout.addWidget(self.recent_button)
+        top_layout.addWidget(self.paste_button)
+        top_layout.addWidget(self.language_button)
+        top_layout.addWidget(self.settings_button)
+        top_layout.addWidget(self.reset_settings_button)
         
-        self.label = QLabel("Ziehe Dateien oder Ordner hierher oder nutze die Buttons oben.", self)
+        self.label = QLabel(self)
         self.label.setAlignment(Qt.AlignCenter)
         self.label.setWordWrap(True)
         
+        self.pattern_edit = QLineEdit(self)
+        self.pattern_edit.returnPressed.connect(self.add_pattern_from_edit)
+        self.pattern_button = QPushButton(self)
+        self.pattern_button.clicked.connect(self.add_pattern_from_edit)
+        
+        pattern_layout = QHBoxLayout()
+        pattern_layout.addWidget(self.pattern_edit)
+        pattern_layout.addWidget(self.pattern_button)
+        
         self.file_list = QListWidget(self)
-        self.file_list.setToolTip("Geladene Dateien")
+        # Große Listen: gleiche Zeilenhöhe und schrittweises Layout halten das Zeichnen flüssig
+        self.file_list.setUniformItemSizes(True)
+        self.file_list.setLayoutMode(QListWidget.Batched)
+        self.file_list.currentItemChanged.connect(self.update_debug_panel)
+        # Reihenfolge per Drag & Drop oder Strg+Pfeil ändern; sie bestimmt die Verarbeitungsreihenfolge
+        # und die erste Datei den Vorschlag für die Sammel-CSV
+        self.file_list.setDragDropMode(QAbstractItemView.InternalMove)
+        self.file_list.model().rowsMoved.connect(self.sync_file_order)
+        
+        # Fehler und Warnungen des letzten Laufs: sortierbar per Klick auf die Überschrift, filterbar nach Schwere
+        self.error_table = QTableWidget(0, 4, self)
+        self.error_table.setEditTriggers(QTableWidget.NoEditTriggers)
+        self.error_table.setSelectionBehavior(QAbstractItemView.SelectRows)
+        self.error_table.setWordWrap(False)
+        self.error_table.horizontalHeader().setSectionResizeMode(QHeaderView.Interactive)
+        self.error_table.horizontalHeader().setStretchLastSection(True)
+        self.error_table.horizontalHeader().sortIndicatorChanged.connect(self.filter_errors)
+        self.error_table.setSortingEnabled(True)
+        self.error_table.setVisible(False)
+        self.error_table.cellClicked.connect(self.show_file_of_error)
+        
+        self.error_filter_combo = QComboBox(self)
+        self.error_filter_combo.addItems(["" for _ in ERROR_FILTERS])
+        self.error_filter_combo.setVisible(False)
+        self.error_filter_combo.currentIndexChanged.connect(self.filter_errors)
+        
+        self.aggregate_button = QPushButton(self)
+        self.aggregate_button.clicked.connect(self.export_aggregate)
+        
+        self.compare_button = QPushButton(self)
+        self.compare_button.clicked.connect(self.compare_with_last_export)
+        
+        self.export_errors_button = QPushButton(self)
+        self.export_errors_button.setVisible(False)
+        self.export_errors_button.clicked.connect(self.export_error_list)
         
-        self.remove_button = QPushButton("Entfernen", self)
-        self.remove_button.setToolTip("Ausgewählte Dateien entfernen.")
+        self.remove_button = QPushButton(self)
         self.remove_button.clicked.connect(self.remove_selected_files)
         
-        self.process_button = QPushButton("Los", self)
-        self.process_button.setToolTip("Verarbeitung starten.")
-        self.process_button.clicked.connect(self.process_all_files)
+        self.process_button = QPushButton(self)
+        self.process_button.clicked.connect(lambda: self.process_all_files())
+        
+        self.tsv_button = QPushButton(self)
+        self.tsv_button.clicked.connect(lambda: self.process_all_files("tsv"))
+        
+        # Nur während eines Exports sichtbar
+        self.cancel_export_button = QPushButton(self)
+        self.cancel_export_button.setVisible(False)
+        self.cancel_export_button.clicked.connect(self.cancel_export)
+        
+        self.portal_button = QPushButton(self)
+        self.portal_button.clicked.connect(self.copy_for_portal)
+        
+        self.template_button = QPushButton(self)
+        self.template_button.clicked.connect(self.export_template)
+        
+        self.debug_button = QPushButton(self)
+        self.debug_button.setCheckable(True)
+        self.debug_button.toggled.connect(self.toggle_debug_panel)
+        
+        # Ohne automatisches Parsen (Einstellungen) erst viele Dateien sammeln und dann einmal parsen
+        self.parse_now_button = QPushButton(self)
+        self.parse_now_button.setVisible(False)
+        self.parse_now_button.clicked.connect(self.parse_now)
+        
+        self.rename_button = QPushButton(self)
+        self.rename_button.clicked.connect(self.rename_audio_files)
+        
+        self.swap_button = QPushButton(self)
+        self.swap_button.clicked.connect(self.swap_selected_track)
+        
+        self.swap_all_button = QPushButton(self)
+        self.swap_all_button.clicked.connect(self.swap_all_tracks)
+        
+        self.find_replace_button = QPushButton(self)
+        self.find_replace_button.clicked.connect(self.find_replace_tracks)
+        
+        self.musicbrainz_button = QPushButton(self)
+        self.musicbrainz_button.setVisible(self.config.get("musicbrainz_enabled", False))
+        self.musicbrainz_button.clicked.connect(self.lookup_selected_track)
+        
+        self.append_checkbox = QCheckBox(self)
+        self.append_checkbox.toggled.connect(self.toggle_append_mode)
+        
+        self.report_checkbox = QCheckBox(self)
+        
+        # Welche Datei den Namen der Ausgabe bestimmt (output_{base}.csv), ohne die Liste umzusortieren
+        self.base_file_label = QLabel(self)
+        self.base_file_combo = QComboBox(self)
         
         bottom_layout = QHBoxLayout()
+        bottom_layout.addWidget(self.base_file_label)
+        bottom_layout.addWidget(self.base_file_combo)
+        bottom_layout.addWidget(self.append_checkbox)
         bottom_layout.addWidget(self.remove_button)
+        bottom_layout.addWidget(self.debug_button)
+        bottom_layout.addWidget(self.parse_now_button)
+        bottom_layout.addWidget(self.rename_button)
+        bottom_layout.addWidget(self.swap_button)
+        bottom_layout.addWidget(self.swap_all_button)
+        bottom_layout.addWidget(self.find_replace_button)
+        bottom_layout.addWidget(self.musicbrainz_button)
+        bottom_layout.addWidget(self.report_checkbox)
+        bottom_layout.addWidget(self.aggregate_button)
+        bottom_layout.addWidget(self.compare_button)
         bottom_layout.addWidget(self.process_button)
+        bottom_layout.addWidget(self.tsv_button)
+        bottom_layout.addWidget(self.cancel_export_button)
+        bottom_layout.addWidget(self.portal_button)
+        bottom_layout.addWidget(self.template_button)
+        
+        self.debug_table = QTableWidget(0, 7, self)
+        self.debug_table.setEditTriggers(QTableWidget.NoEditTriggers)
+        # Ganze Zeile markieren; Pfeiltasten wählen, Enter/Doppelklick korrigiert, Entf setzt zurück
+        self.debug_table.setSelectionBehavior(QAbstractItemView.SelectRows)
+        self.debug_table.setSelectionMode(QAbstractItemView.SingleSelection)
+        self.debug_table.cellDoubleClicked.connect(lambda row, column: self.edit_selected_track())
+        # Lange Namen werden abgeschnitten (voller Text im Tooltip), Labelcode und Dauer bleiben schmal
+        self.debug_table.setWordWrap(False)
+        self.debug_table.setTextElideMode(Qt.ElideRight)
+        self.setup_debug_columns()
+        # Feste Zeilenhöhe: Qt muss bei tausenden Zeilen nicht jede Zeile vermessen
+        self.debug_table.verticalHeader().setSectionResizeMode(QHeaderView.Fixed)
+        self.debug_table.verticalHeader().setDefaultSectionSize(self.fontMetrics().height() + 6)
+        self.debug_table.setVisible(False)
+        
+        # Tastenkürzel gelten nur im jeweiligen Widget, damit Textfelder Entf und Enter behalten
+        for widget, keys, slot in [(self.file_list, [QKeySequence.Delete], self.remove_selected_files),
+                                   (self.file_list, [Qt.CTRL + Qt.Key_Up], lambda: self.move_current_file(-1)),
+                                   (self.file_list, [Qt.CTRL + Qt.Key_Down], lambda: self.move_current_file(1)),
+                                   (self.debug_table, [Qt.Key_Return, Qt.Key_Enter], self.edit_selected_track),
+                                   (self.debug_table, [QKeySequence.Delete], self.reset_selected_track)]:
+            for key in keys:
+                shortcut = QShortcut(QKeySequence(key), widget)
+                shortcut.setContext(Qt.WidgetShortcut)
+                shortcut.activated.connect(slot)
         
         self.progress_bar = QProgressBar(self)
         self.progress_bar.setValue(0)
         self.progress_bar.setVisible(False)
         
+        # Ständig sichtbar: Zähler rechts, letzte Aktion links
+        self.status_bar = QStatusBar(self)
+        self.status_bar.setSizeGripEnabled(False)
+        self.status_counts = QLabel(self)
+        self.status_bar.addPermanentWidget(self.status_counts)
+        self.status_bar.addPermanentWidget(self.progress_bar)
+        
+        # Kurze Hinweise unten rechts, die von selbst verschwinden; Rückfragen bleiben Dialoge
+        self.toast_label = QLabel(self)
+        self.toast_label.setStyleSheet("background: rgba(40, 40, 40, 220); color: white; padding: 6px 10px;"
+                                       " border-radius: 4px;")
+        self.toast_label.setVisible(False)
+        self.toast_queue = []
+        self.toast_timer = QTimer(self)
+        self.toast_timer.setSingleShot(True)
+        self.toast_timer.timeout.connect(self.next_toast)
+        
         main_layout = QVBoxLayout()
         main_layout.addLayout(top_layout)
         main_layout.addSpacing(10)
         main_layout.addWidget(self.label)
         main_layout.addSpacing(10)
+        main_layout.addLayout(pattern_layout)
         main_layout.addWidget(self.file_list)
+        main_layout.addWidget(self.error_filter_combo)
+        main_layout.addWidget(self.error_table)
+        main_layout.addWidget(self.export_errors_button)
         main_layout.addSpacing(10)
         main_layout.addLayout(bottom_layout)
         main_layout.addSpacing(10)
-        main_layout.addWidget(self.progress_bar)
+        main_layout.addWidget(self.debug_table)
+        main_layout.addWidget(self.status_bar)
         
         self.setLayout(main_layout)
         
         self.resize(600, 400)
         
         self.file_paths = []
+        self.append_file = None
+        self.dirty = False  # Dateien hinzugefügt, aber seit dem letzten Lauf nicht verarbeitet
+        self.file_errors = {}  # Pfad -> Fehler aus dem letzten Lauf
+        self.debug_rows = []
+        self.lookup_workers = []
+        self.export_worker = None  # Laufender Export, siehe ExportWorker
+        self.precheck_worker = None  # Prüfung vor dem Export, siehe PrecheckWorker
+        self.close_after_export = False
+        self.duplicate_count = 0  # Beim letzten Hinzufügen übersprungene, bereits geladene Dateien
+        self.parse_pending = False  # Dateien hinzugefügt, aber wegen ausgeschaltetem Auto-Parsen noch nicht geparst
+        self.run_rows = {}  # Pfad -> exportierte Zeilen des letzten Laufs, für Tracks und Gesamtdauer in der Statusleiste
+        
+        self.retranslate_ui()
+        self.label.setText(self.text("drop_hint"))
+        self.update_status()
+        
+        self.offer_draft_recovery()
+        # Regelmäßig einen Entwurf sichern, damit ein Absturz keine Arbeit kostet (0 = aus)
+        self.autosave_timer = QTimer(self)
+        self.autosave_timer.timeout.connect(self.autosave_draft)
+        interval = self.config.get("autosave_interval", 60)
+        if interval:
+            self.autosave_timer.start(int(interval) * 1000)
+    
+    def text(self, key, **kwargs):
+        return tr(key, self.language, **kwargs)
+    
+    def retranslate_ui(self):
+        self.setWindowTitle(self.text("window_title"))
+        for widget, key in [(self.output_button, "output_button"),
+                            (self.reload_button, "reload_button"),
+                            (self.reassign_button, "reassign_button"),
+                            (self.labelcodes_button, "labelcodes_button"),
+                            (self.add_labelcodes_button, "add_labelcodes_button"),
+                            (self.headers_button, "headers_button"),
+                            (self.file_select_button, "file_select_button"),
+                            (self.recent_button, "recent_button"),
+                            (self.paste_button, "paste_button"),
+                            (self.language_button, "language_button"),
+                            (self.remove_button, "remove_button"),
+                            (self.process_button, "process_button"),
+                            (self.reset_settings_button, "reset_settings_button"),
+                            (self.settings_button, "settings_button"),
+                            (self.tsv_button, "tsv_button"),
+                            (self.cancel_export_button, "cancel_export_button"),
+                            (self.rules_button, "rules_button"),
+                            (self.portal_button, "portal_button"),
+                            (self.template_button, "template_button"),
+                            (self.debug_button, "debug_button"),
+                            (self.rename_button, "rename_button"),
+                            (self.swap_button, "swap_button"),
+                            (self.swap_all_button, "swap_all_button"),
+                            (self.find_replace_button, "find_replace_button"),
+                            (self.musicbrainz_button, "musicbrainz_button"),
+                            (self.export_errors_button, "export_errors_button"),
+                            (self.aggregate_button, "aggregate_button"),
+                            (self.compare_button, "compare_button"),
+                            (self.append_checkbox, "append_checkbox"),
+                            (self.parse_now_button, "parse_now_button"),
+                            (self.pattern_button, "pattern_button"),
+                            (self.report_checkbox, "report_checkbox")]:
+            widget.setText(self.text(key))
+            widget.setToolTip(self.text(key + "_tip"))
+        self.file_list.setToolTip(self.text("file_list_tip"))
+        self.error_table.setToolTip(self.text("error_list_tip"))
+        self.error_table.setHorizontalHeaderLabels(self.text("error_table_headers"))
+        for i, severity in enumerate(ERROR_FILTERS):
+            self.error_filter_combo.setItemText(i, self.text("error_filter_" + severity))
+        self.error_filter_combo.setToolTip(self.text("error_filter_tip"))
+        self.base_file_label.setText(self.text("base_file_label"))
+        self.base_file_combo.setToolTip(self.text("base_file_tip"))
+        self.pattern_edit.setPlaceholderText(self.text("pattern_placeholder"))
+        self.set_debug_header_labels()
+    
+    def toggle_language(self):
+        self.language = Language.EN if self.language == Language.DE else Language.DE
+        self.config["language"] = self.language.value
+        save_config(self.config)
+        self.retranslate_ui()
+        self.label.setText(self.text("drop_hint"))
+        self.update_status()
+    
+    def load_label_dict(self):
+        # Hauptdatei plus zusätzliche Labelcodes-Dateien; die Herkunft wird im Debug-Tooltip gezeigt
+        files = [self.labelcodes_file] + self.config.get("extra_labelcodes_files", [])
+        self.label_dict, self.label_sources, conflicts = merge_labelcodes(files)
+        for label, code, source, other_code, other_source in conflicts:
+            log_error(f"Labelcode-Konflikt für {label}: {code} aus {source} ersetzt {other_code} aus {other_source}")
+        return conflicts
+    
+    def show_label_conflicts(self, conflicts):
+        if not conflicts:
+            return
+        box = QMessageBox(QMessageBox.Warning, self.text("labelcodes_conflicts_title"),
+                          self.text("labelcodes_conflicts_text", count=len(conflicts)), parent=self)
+        box.setDetailedText("\n".join(self.text("labelcodes_conflict_line", label=label, code=code, file=source,
+                                                 other_code=other_code, other_file=other_source)
+                                       for label, code, source, other_code, other_source in conflicts))
+        box.exec_()
+    
+    def add_labelcodes_file(self):
+        path, _ = QFileDialog.getOpenFileName(self, self.text("add_labelcodes_button"), "",
+                                              "Labelcodes (*.txt *.json)")
+        if not path:
+            return
+        path = canonical_path(path)
+        extra_files = self.config.get("extra_labelcodes_files", [])
+        if path in extra_files or path == canonical_path(self.labelcodes_file):
+            self.label.setText(self.text("labelcodes_file_known", path=path))
+            return
+        self.config["extra_labelcodes_files"] = extra_files + [path]
+        save_config(self.config)
+        conflicts = self.load_label_dict()
+        self.update_debug_panel(self.file_list.currentItem())
+        self.label.setText(self.text("labelcodes_file_added", path=path, count=len(self.label_dict)))
+        self.show_label_conflicts(conflicts)
     
     def reload_labelcodes(self):
-        self.label_dict = load_labelcodes(self.labelcodes_file)
-        self.label.setText("Labelcodes wurden neu geladen.")
+        conflicts = self.load_label_dict()
+        self.label.setText(self.text("labelcodes_reloaded"))
+        self.show_label_conflicts(conflicts)
+    
+    def reassign_labelcodes(self):
+        # Nur die Zuordnung neu berechnen, die Labelcodes-Datei wird dabei nicht neu gelesen
+        matched = total = 0
+        for input_file in self.file_paths:
+            if check_input_file(input_file, self.language):
+                continue
+            try:
+                rows = debug_parse_file(input_file, self.label_dict, self.parse_options)
+            except Exception as e:
+                log_error(f"Labelcodes für {input_file} nicht zugeordnet: {e}")
+                continue
+            total += len(rows)
+            matched += sum(1 for row in rows if row["label_code"])
+        self.update_debug_panel(self.file_list.currentItem())
+        self.label.setText(self.text("labelcodes_reassigned", matched=matched, total=total))
+    
+    def edit_column_headers(self):
+        dialog = ColumnHeadersDialog(self.csv_columns, self.config.get("csv_headers", {}), self.language, self)
+        if dialog.exec_() == ColumnHeadersDialog.Accepted:
+            self.config["csv_headers"] = dialog.headers()
+            save_config(self.config)
+            self.label.setText(self.text("headers_saved"))
+    
+    def edit_rules(self):
+        dialog = RulesDialog(self.config.get("field_replacements", []), self.parse_options, self.language, self)
+        if dialog.exec_() != RulesDialog.Accepted:
+            return
+        self.config["field_replacements"] = dialog.rules()
+        save_config(self.config)
+        self.parse_options.field_replacements = valid_replacements(self.config["field_replacements"])
+        self.dirty = True
+        self.update_debug_panel(self.file_list.currentItem())
+        self.label.setText(self.text("rules_saved", count=len(self.config["field_replacements"])))
+    
+    def manage_labelcodes(self):
+        dialog = LabelcodesDialog(self.labelcodes_file, self.language, self)
+        if dialog.exec_() == LabelcodesDialog.Accepted:
+            self.load_label_dict()
+            self.update_debug_panel(self.file_list.currentItem())
+            self.label.setText(self.text("labelcodes_saved"))
     
     def choose_output_directory(self):
-        directory = QFileDialog.getExistingDirectory(self, "Ausgabeort wählen", self.output_dir)
+        directory = QFileDialog.getExistingDirectory(self, self.text("output_button"), self.output_dir)
         if directory:
             self.output_dir = directory
-            self.label.setText(f"Ausgabeort: {self.output_dir}")
+            self.label.setText(self.text("output_dir", path=self.output_dir))
+    
+    def reset_settings(self):
+        answer = QMessageBox.question(self, self.text("reset_settings_button"), self.text("reset_settings_confirm"))
+        if answer != QMessageBox.Yes:
+            return
+        self.config = default_settings(self.config)
+        save_config(self.config)
+        self.apply_config()
+        self.update_status(self.text("settings_reset"))
+        self.show_toast(self.text("settings_reset"))
+    
+    def edit_settings(self):
+        dialog = SettingsDialog(dict(self.config, language=self.language.value), self.language, self)
+        if dialog.exec_() != SettingsDialog.Accepted:
+            return
+        self.config.update(dialog.values())
+        save_config(self.config)
+        self.apply_config()
+        self.update_status(self.text("settings_saved"))
+    
+    def apply_config(self):
+        # Geänderte Einstellungen übernehmen (zurückgesetzt oder aus dem Einstellungsfenster)
+        paths = resolve_paths(self.config, self.path_overrides)
+        self.output_dir = paths["default_output_dir"]
+        self.labelcodes_file = paths["labelcodes_file"]
+        self.csv_columns = self.config.get("csv_columns", ["Index", "Titel", "Künstler", "Labelcode", "Dauer"])
+        self.load_label_dict()
+        overrides = self.parse_options.overrides  # Korrekturen einzelner Tracks sind keine Einstellungen
+        self.parse_options = ParseOptions.from_config(self.config)
+        self.parse_options.overrides = overrides
+        configure_file_logging(self.config.get("file_log_level", "aus"))
+        self.setup_debug_columns()
+        for row in range(self.file_list.count()):
+            item = self.file_list.item(row)
+            item.setText(self.file_item_text(item))
+        if self.config.get("auto_parse", True) and self.parse_pending:
+            self.parse_now()
+        self.musicbrainz_button.setVisible(self.config.get("musicbrainz_enabled", False))
+        interval = self.config.get("autosave_interval", 60)
+        if interval:
+            self.autosave_timer.start(int(interval) * 1000)
+        else:
+            self.autosave_timer.stop()
+        language = language_from_config(self.config.get("language"))
+        if language != self.language:
+            self.language = language
+            self.retranslate_ui()
+        self.update_debug_panel(self.file_list.currentItem())
+    
+    def set_parse_pending(self, pending):
+        self.parse_pending = pending
+        self.parse_now_button.setVisible(pending)
+    
+    def parse_now(self):
+        self.set_parse_pending(False)
+        if self.debug_table.isVisible():
+            self.update_debug_panel(self.file_list.currentItem())
+        else:
+            # Ergebnis des Parsens ist die Debug-Ansicht; toggle_debug_panel aktualisiert sie
+            self.debug_button.setChecked(True)
+    
+    def toggle_append_mode(self, checked):
+        if not checked:
+            self.append_file = None
+            self.label.setText(self.text("append_off"))
+            return
+        default_name = self.output_dir
+        if self.file_paths:
+            base_name = os.path.splitext(os.path.basename(self.base_file()))[0]
+            default_name = os.path.join(self.output_dir, f"output_{base_name}.csv")
+        csv_file = self.save_file_name(self.text("append_dialog_title"), default_name,
+                                       ["CSV Files (*.csv)", "TSV Files (*.tsv)"],
+                                       options=QFileDialog.DontConfirmOverwrite)
+        if csv_file:
+            self.append_file = csv_file
+            self.label.setText(self.text("append_target", path=self.append_file))
+        else:
+            self.append_checkbox.setChecked(False)
+    
+    def sync_file_order(self, *args):
+        self.file_paths = [self.file_list.item(row).data(Qt.UserRole) for row in range(self.file_list.count())]
+        self.update_base_file_combo()
+    
+    def update_base_file_combo(self):
+        # Auswahl bleibt erhalten, solange die Datei geladen ist; sonst ist die erste Datei die Basis
+        selected = self.base_file_combo.currentData()
+        self.base_file_combo.blockSignals(True)
+        self.base_file_combo.clear()
+        for path in self.file_paths:
+            self.base_file_combo.addItem(os.path.basename(path), path)
+        if selected in self.file_paths:
+            self.base_file_combo.setCurrentIndex(self.file_paths.index(selected))
+        self.base_file_combo.blockSignals(False)
+    
+    def base_file(self):
+        selected = self.base_file_combo.currentData()
+        if selected in self.file_paths:
+            return selected
+        return self.file_paths[0] if self.file_paths else None
+    
+    def move_current_file(self, step):
+        row = self.file_list.currentRow()
+        target = row + step
+        if row < 0 or not 0 <= target < self.file_list.count():
+            return
+        item = self.file_list.takeItem(row)
+        self.file_list.insertItem(target, item)
+        self.file_list.setCurrentRow(target)
+        self.sync_file_order()
     
     def select_files(self):
-        files, _ = QFileDialog.getOpenFileNames(self, "Dateien auswählen", "", "Text Files (*.txt)")
+        files, _ = QFileDialog.getOpenFileNames(self, self.text("select_dialog_title"), "", "Text Files (*.txt *.cue)")
         if files:
-            added_count = 0
-            for f in files:
-                if f not in self.file_paths:
-                    self.file_paths.append(f)
-                    self.file_list.addItem(f)
-                    added_count += 1
+            self.remember_recent(files)
+            added_count = self.add_files(files)
             if self.file_paths:
-                self.label.setText(f"{len(self.file_paths)} Datei(en) geladen. ({added_count} neu)")
+                self.label.setText(self.text("files_loaded", count=len(self.file_paths), added=added_count)
+                                   + self.duplicates_hint())
+            else:
+                self.label.setText(self.text("no_files"))
+    
+    def paste_from_clipboard(self):
+        lines = [line.strip().strip('"') for line in QApplication.clipboard().text().splitlines() if line.strip()]
+        if not lines:
+            self.label.setText(self.text("paste_empty"))
+            return
+        box = QMessageBox(QMessageBox.Question, self.text("paste_button"),
+                          self.text("paste_choose_mode", count=len(lines)), parent=self)
+        paths_button = box.addButton(self.text("paste_as_paths"), QMessageBox.AcceptRole)
+        tracks_button = box.addButton(self.text("paste_as_tracks"), QMessageBox.AcceptRole)
+        box.addButton(self.text("cancel"), QMessageBox.RejectRole)
+        # Vorauswahl: Pfade, wenn alle Zeilen existieren
+        box.setDefaultButton(paths_button if all(os.path.exists(line) for line in lines) else tracks_button)
+        box.exec_()
+        clicked = box.clickedButton()
+        if clicked == paths_button:
+            files = []
+            for line in lines:
+                if os.path.isdir(line):
+                    files.extend(self.import_folder(line))
+                elif line.lower().endswith(INPUT_EXTENSIONS):
+                    files.append(line)
+            added_count = self.add_files(files)
+            self.label.setText(self.text("files_loaded", count=len(self.file_paths), added=added_count)
+                               + self.duplicates_hint())
+        elif clicked == tracks_button:
+            # Im Speicher einlesen und direkt exportieren, ohne Zwischendatei; der Name bestimmt nur die Ausgabedatei
+            name = datetime.now().strftime("zwischenablage_%Y%m%d_%H%M%S")
+            settings = self.export_settings("csv", self.config.get("strict_mode", False), None)
+            summary, errors = process_single_file(name, text="\n".join(lines), **settings)
+            self.file_errors = {name: errors} if errors else {}
+            self.show_errors()
+            self.label.setText(summary)
+    
+    def remember_recent(self, paths, limit=10):
+        # Zuletzt verwendete Dateien und Ordner, neueste zuerst
+        recent = [canonical_path(p) for p in paths]
+        recent += [p for p in self.config.get("recent_paths", []) if p not in recent]
+        self.config["recent_paths"] = recent[:limit]
+        save_config(self.config)
+    
+    def fill_recent_menu(self):
+        self.recent_menu.clear()
+        recent = self.config.get("recent_paths", [])
+        if not recent:
+            self.recent_menu.addAction(self.text("recent_empty")).setEnabled(False)
+        for path in recent:
+            self.recent_menu.addAction(path, lambda path=path: self.open_recent(path))
+    
+    def open_recent(self, path):
+        if not os.path.exists(path):
+            self.config["recent_paths"] = [p for p in self.config.get("recent_paths", []) if p != path]
+            save_config(self.config)
+            self.label.setText(self.text("recent_missing", path=path))
+            return
+        self.remember_recent([path])
+        files = self.import_folder(path) if os.path.isdir(path) else [path]
+        added_count = self.add_files(files)
+        self.label.setText(self.text("files_loaded", count=len(self.file_paths), added=added_count)
+                           + self.duplicates_hint())
+        self.update_debug_panel(self.file_list.currentItem())
+    
+    def add_file(self, file_path):
+        file_path = canonical_path(file_path)
+        if file_path in self.file_paths:
+            return False
+        self.file_paths.append(file_path)
+        item = QListWidgetItem()
+        item.setData(Qt.UserRole, file_path)
+        # Kodierung und Zeilenenden nur einmal beim Hinzufügen bestimmen
+        item.setData(Qt.UserRole + 1, describe_text_file(file_path, self.language))
+        item.setText(self.file_item_text(item))
+        item.setToolTip(file_path)
+        self.file_list.addItem(item)
+        self.dirty = True
+        if not self.config.get("auto_parse", True):
+            self.set_parse_pending(True)
+        return True
+    
+    def file_item_text(self, item):
+        info = item.data(Qt.UserRole + 1)
+        text = self.display_path(item.data(Qt.UserRole))
+        return f"{text}  [{info}]" if info else text
+    
+    def display_path(self, path):
+        mode = self.config.get("path_display", "absolut")
+        if mode == "name":
+            return os.path.basename(path)
+        if mode == "relativ":
+            try:
+                return os.path.relpath(path, self.config.get("path_display_base") or ".")
+            except ValueError:
+                # Anderes Laufwerk unter Windows
+                return path
+        return path
+    
+    def add_files(self, paths):
+        # Bei sehr vielen Dateien erst nachfragen, damit die Oberfläche nicht unbemerkt träge wird
+        requested = list(dict.fromkeys(canonical_path(p) for p in paths))
+        new_paths = [p for p in requested if p not in self.file_paths]
+        self.duplicate_count = len(paths) - len(new_paths)
+        limit = self.config.get("max_file_count", 5000)
+        if limit and len(self.file_paths) + len(new_paths) > limit:
+            answer = QMessageBox.question(self, self.text("file_limit_title"),
+                                          self.text("file_limit_text", count=len(new_paths), limit=limit),
+                                          QMessageBox.Yes | QMessageBox.No)
+            if answer != QMessageBox.Yes:
+                return 0
+        added_count = sum(1 for p in new_paths if self.add_file(p))
+        log_info(f"{added_count} Datei(en) hinzugefügt, {self.duplicate_count} bereits vorhanden.")
+        self.update_base_file_combo()
+        self.update_status(self.text("status_files_added", count=added_count))
+        if added_count:
+            self.show_toast(self.text("toast_files_added", count=added_count))
+        return added_count
+    
+    def show_toast(self, message):
+        self.toast_queue.append(message)
+        if not self.toast_timer.isActive():
+            self.next_toast()
+    
+    def next_toast(self):
+        if not self.toast_queue:
+            self.toast_label.setVisible(False)
+            return
+        self.toast_label.setText(self.toast_queue.pop(0))
+        self.toast_label.adjustSize()
+        self.toast_label.move(self.width() - self.toast_label.width() - 12,
+                              self.height() - self.toast_label.height() - self.status_bar.height() - 12)
+        self.toast_label.raise_()
+        self.toast_label.setVisible(True)
+        self.toast_timer.start(TOAST_DURATION)
+    
+    def update_status(self, action=None):
+        # Tracks und Dauer stammen aus dem letzten Lauf, da erst die Verarbeitung alle Dateien parst
+        rows = [row for path in self.file_paths for row in self.run_rows.get(path, {}).values()]
+        total = sum(duration_seconds(parse_duration(row["duration"]) or 0) for row in rows)
+        severities = [error_severity(error) for errors in self.file_errors.values() for error in errors]
+        self.status_counts.setText(self.text("status_counts", files=len(self.file_paths), tracks=len(rows),
+                                             errors=severities.count("fehler"), warnings=severities.count("warnung"),
+                                             duration=format_seconds(total)))
+        if action:
+            self.status_bar.showMessage(action)
+    
+    def duplicates_hint(self):
+        if not self.duplicate_count:
+            return ""
+        return "\n" + self.text("duplicates_skipped", count=self.duplicate_count)
+    
+    def add_patterns(self, patterns):
+        files = []
+        unmatched = []
+        for pattern in patterns:
+            if os.path.isdir(pattern):
+                files.extend(self.import_folder(pattern))
+                continue
+            matches = expand_pattern(pattern)
+            if not matches:
+                unmatched.append(pattern)
+            files.extend(matches)
+        added_count = self.add_files(files)
+        message = self.text("files_loaded", count=len(self.file_paths), added=added_count) + self.duplicates_hint()
+        if unmatched:
+            message += "\n" + self.text("pattern_no_match", patterns=", ".join(unmatched))
+        self.label.setText(message)
+    
+    def import_folder(self, directory):
+        # Vor dem Hinzufügen zusammenfassen, was im Ordner gefunden wurde
+        files, ignored = scan_folder(directory)
+        problems = sum(1 for f in files if check_input_file(f, self.language))
+        known = sum(1 for f in files if canonical_path(f) in self.file_paths)
+        box = QMessageBox(QMessageBox.Question, self.text("folder_import_title"),
+                          self.text("folder_import_text", path=directory, found=len(files),
+                                    readable=len(files) - problems, problems=problems, known=known, ignored=ignored),
+                          parent=self)
+        continue_button = box.addButton(self.text("folder_import_continue"), QMessageBox.AcceptRole)
+        box.addButton(self.text("cancel"), QMessageBox.RejectRole)
+        box.exec_()
+        if box.clickedButton() != continue_button:
+            return []
+        return files
+    
+    def add_pattern_from_edit(self):
+        pattern = self.pattern_edit.text().strip()
+        if not pattern:
+            return
+        self.add_patterns([pattern])
+        self.pattern_edit.clear()
+    
+    def setup_debug_columns(self):
+        # Kompakt: eine Zeile pro Track als Text, sonst die einzelnen Spalten
+        compact = self.config.get("compact_view", False)
+        self.debug_table.setColumnCount(1 if compact else 7)
+        header = self.debug_table.horizontalHeader()
+        header.setSectionResizeMode(QHeaderView.Stretch)
+        if not compact:
+            for column, width in [(2, 80), (5, 70), (6, 60)]:
+                header.setSectionResizeMode(column, QHeaderView.Fixed)
+                self.debug_table.setColumnWidth(column, width)
+        self.set_debug_header_labels()
+    
+    def set_debug_header_labels(self):
+        if self.config.get("compact_view", False):
+            self.debug_table.setHorizontalHeaderLabels([self.text("compact_header")])
+        else:
+            self.debug_table.setHorizontalHeaderLabels(self.text("debug_headers"))
+    
+    def toggle_debug_panel(self, checked):
+        self.debug_table.setVisible(checked)
+        if checked:
+            self.update_debug_panel(self.file_list.currentItem())
+    
+    def update_debug_panel(self, item, previous=None):
+        if not self.debug_table.isVisible():
+            return
+        self.debug_table.setRowCount(0)
+        self.debug_rows = []
+        if item is None:
+            return
+        if self.parse_pending:
+            self.label.setText(self.text("parse_pending", count=len(self.file_paths)))
+            return
+        input_file = item.data(Qt.UserRole)
+        problem = check_input_file(input_file, self.language)
+        if problem:
+            self.label.setText(problem)
+            return
+        try:
+            rows = debug_parse_file(input_file, self.label_dict, self.parse_options)
+        except Exception as e:
+            self.label.setText(self.text("debug_error", path=input_file, error=e))
+            return
+        self.debug_rows = rows
+        if not rows:
+            self.label.setText(self.text("debug_no_rows", path=input_file))
+        self.debug_table.setUpdatesEnabled(False)
+        self.debug_table.setRowCount(len(rows))
+        for r, row in enumerate(rows):
+            if self.config.get("compact_view", False):
+                values = [f"{row['index']} · {row['title']} – {row['artist']} "
+                          f"({row['duration'] or row['duration_raw']}) [{row['label_code'] or '-'}]"]
             else:
-                self.label.setText("Keine Dateien geladen.")
+                values = [row["filename"], " | ".join(row["tokens"]), " ".join(row["index_tokens"]),
+                          row["title"], row["artist"], row["label_code"],
+                          row["duration"] or row["duration_raw"]]
+            tooltip = self.parse_details_tooltip(row)
+            if row["swap_hint"]:
+                tooltip += "\n" + self.text("swap_hint_tip")
+            for c, value in enumerate(values):
+                cell = QTableWidgetItem(value)
+                cell.setToolTip(tooltip)
+                if row["swap_hint"]:
+                    cell.setForeground(QBrush(QColor("darkorange")))
+                self.debug_table.setItem(r, c, cell)
+        self.debug_table.setUpdatesEnabled(True)
+        swap_hints = sum(1 for row in rows if row["swap_hint"])
+        if swap_hints:
+            self.label.setText(self.text("swap_hint", count=swap_hints))
+    
+    def parse_details_tooltip(self, row):
+        if row["duration"] is not None:
+            duration = self.text("tooltip_duration", duration=row["duration"], raw=row["duration_raw"])
+        else:
+            duration = self.text("tooltip_duration_invalid", raw=row["duration_raw"])
+        if row["matched_label"] is not None:
+            label = self.text("tooltip_label", code=row["label_code"], label=row["matched_label"], index=row["index"])
+            if len(set(self.label_sources.values())) > 1:
+                label += "\n" + self.text("tooltip_label_source", file=self.label_sources[row["matched_label"]])
+        else:
+            label = self.text("tooltip_no_label", index=row["index"])
+        headers = self.text("debug_headers")
+        field_names = {"index": headers[2], "title": headers[3], "artist": headers[4]}
+        cleanup = [self.text("tooltip_cleanup", field=field_names[name], before=before, after=after)
+                   for name, before, after in row["cleanup"]]
+        return "\n".join([
+            self.text("tooltip_filename", filename=row["filename"]),
+            self.text("tooltip_tokens", tokens=" | ".join(row["tokens"])),
+            self.text("tooltip_fields", index=" ".join(row["index_tokens"]), title=" ".join(row["title_tokens"]),
+                      artist=" ".join(row["artist_tokens"])),
+            *cleanup,
+            duration,
+            label,
+        ])
+    
+    def rename_audio_files(self):
+        item = self.file_list.currentItem()
+        if item is None:
+            self.label.setText(self.text("rename_no_selection"))
+            return
+        input_file = item.data(Qt.UserRole)
+        template = self.config.get("rename_template", "{index}_{titel}_{kuenstler}.{ext}")
+        try:
+            plan = plan_renames(input_file, self.label_dict, template, self.parse_options)
+        except Exception as e:
+            self.label.setText(self.text("processing_error", error=e))
+            log_error("Exception: " + traceback.format_exc())
+            return
+        if not plan:
+            self.label.setText(self.text("rename_nothing"))
+            return
+        preview = []
+        for old_path, new_path, problem in plan:
+            line = f"{os.path.basename(old_path)} -> {os.path.basename(new_path)}"
+            if problem:
+                line += f"  [{self.text('rename_problem_' + problem)}]"
+            preview.append(line)
+        ok_count = sum(1 for _, _, problem in plan if problem is None)
+        box = QMessageBox(QMessageBox.Question, self.text("rename_button"),
+                          self.text("rename_confirm", count=ok_count, skipped=len(plan) - ok_count),
+                          QMessageBox.Ok | QMessageBox.Cancel, self)
+        box.setDetailedText("\n".join(preview))
+        if box.exec_() != QMessageBox.Ok:
+            return
+        try:
+            renamed = apply_renames(input_file, plan)
+        except OSError as e:
+            self.label.setText(self.text("processing_error", error=e))
+            log_error("Exception: " + traceback.format_exc())
+            return
+        self.label.setText(self.text("rename_done", count=renamed))
+        self.update_debug_panel(item)
+    
+    def selected_debug_row(self):
+        row_index = self.debug_table.currentRow()
+        if not self.debug_table.isVisible() or not 0 <= row_index < len(self.debug_rows):
+            return None
+        return self.debug_rows[row_index]
+    
+    def edit_selected_track(self):
+        row = self.selected_debug_row()
+        if row is None:
+            return
+        dialog = TrackEditDialog(row, self.language, self)
+        if dialog.exec_() != TrackEditDialog.Accepted:
+            return
+        self.set_track_override(row["filename"], dialog.values())
+        self.label.setText(self.text("track_edited", filename=row["filename"]))
+    
+    def reset_selected_track(self):
+        row = self.selected_debug_row()
+        if row is None or row["filename"] not in self.parse_options.overrides:
+            return
+        self.set_track_override(row["filename"], None)
+        self.label.setText(self.text("track_reset", filename=row["filename"]))
+    
+    def swap_override(self, row):
+        return dict(self.parse_options.overrides.get(row["filename"], {}), title=row["artist"], artist=row["title"])
+    
+    def swap_selected_track(self):
+        row = self.selected_debug_row()
+        if row is None:
+            self.label.setText(self.text("swap_no_selection"))
+            return
+        self.set_track_override(row["filename"], self.swap_override(row))
+        self.label.setText(self.text("swapped", count=1))
+    
+    def swap_all_tracks(self):
+        # Systematisch vertauschte Dateien auf einmal korrigieren; danach nur einmal neu parsen
+        rows = self.debug_rows if self.debug_table.isVisible() else []
+        if not rows:
+            self.label.setText(self.text("swap_no_selection"))
+            return
+        for row in rows:
+            self.parse_options.overrides[row["filename"]] = self.swap_override(row)
+        self.dirty = True
+        self.update_debug_panel(self.file_list.currentItem())
+        self.label.setText(self.text("swapped", count=len(rows)))
+    
+    def find_replace_tracks(self):
+        rows = self.debug_rows if self.debug_table.isVisible() else []
+        if not rows:
+            self.label.setText(self.text("swap_no_selection"))
+            return
+        dialog = FindReplaceDialog(rows, self.language, self)
+        if dialog.exec_() != FindReplaceDialog.Accepted:
+            return
+        field_name, changes = dialog.field_name(), dialog.changes()
+        for filename, _, after in changes:
+            self.parse_options.overrides[filename] = dict(self.parse_options.overrides.get(filename, {}),
+                                                          **{field_name: after})
+        self.dirty = True
+        self.update_debug_panel(self.file_list.currentItem())
+        self.label.setText(self.text("find_replace_done", count=len(changes)))
+    
+    def set_track_override(self, filename, values):
+        # Neu parsen und die Auswahl auf derselben Zeile halten
+        current_row = self.debug_table.currentRow()
+        if values:
+            self.parse_options.overrides[filename] = values
+        else:
+            self.parse_options.overrides.pop(filename, None)
+        self.dirty = True
+        self.update_debug_panel(self.file_list.currentItem())
+        self.debug_table.selectRow(current_row)
+    
+    def lookup_selected_track(self):
+        # Optionaler Online-Abgleich, nur aktiv mit "musicbrainz_enabled" in der Config
+        row = self.selected_debug_row()
+        if row is None:
+            self.label.setText(self.text("musicbrainz_no_selection"))
+            return
+        worker = LookupWorker(row, self)
+        worker.lookup_finished.connect(self.show_lookup_result)
+        worker.finished.connect(lambda: self.lookup_workers.remove(worker))
+        self.lookup_workers.append(worker)
+        self.label.setText(self.text("musicbrainz_running"))
+        worker.start()
+    
+    def show_lookup_result(self, row, result):
+        if isinstance(result, Exception):
+            self.label.setText(self.text("musicbrainz_error", error=result))
+            log_error(f"MusicBrainz-Abfrage für {row['filename']} fehlgeschlagen: {result}")
+            return
+        if result is None:
+            self.label.setText(self.text("musicbrainz_no_match", filename=row["filename"]))
+            return
+        title, artist = result
+        answer = QMessageBox.question(self, self.text("musicbrainz_button"),
+                                      self.text("musicbrainz_accept", filename=row["filename"],
+                                                title=title, artist=artist),
+                                      QMessageBox.Yes | QMessageBox.No)
+        if answer != QMessageBox.Yes:
+            return
+        override = dict(self.parse_options.overrides.get(row["filename"], {}), title=title, artist=artist)
+        self.set_track_override(row["filename"], override)
+        self.label.setText(self.text("musicbrainz_accepted", filename=row["filename"]))
     
     def dragEnterEvent(self, event):
         if event.mimeData().hasUrls():
@@ -109,70 +1106,500 @@ class DragDropWindow(QWidget):
             event.ignore()
     
     def dropEvent(self, event):
-        from processing import list_txt_files_in_dir
         urls = event.mimeData().urls()
         if not urls:
             return
-        added_count = 0
+        files = []
         for url in urls:
             file_path = url.toLocalFile()
-            if file_path and not file_path in self.file_paths:
-                if not file_path.lower().endswith('.txt') and not os.path.isdir(file_path):
-                    # Nur txt oder Ordner
+            if file_path:
+                if not file_path.lower().endswith(INPUT_EXTENSIONS) and not os.path.isdir(file_path):
+                    # Nur txt, cue oder Ordner
                     continue
+                self.remember_recent([file_path])
                 if os.path.isdir(file_path):
-                    txt_files = list_txt_files_in_dir(file_path)
-                    for tf in txt_files:
-                        if tf not in self.file_paths:
-                            self.file_paths.append(tf)
-                            self.file_list.addItem(tf)
-                            added_count += 1
+                    files.extend(self.import_folder(file_path))
                 else:
-                    self.file_paths.append(file_path)
-                    self.file_list.addItem(file_path)
-                    added_count += 1
+                    files.append(file_path)
+        added_count = self.add_files(files)
         
         if self.file_paths:
-            self.label.setText(f"{len(self.file_paths)} Datei(en) geladen. (+{added_count} neu)")
+            self.label.setText(self.text("files_dropped", count=len(self.file_paths), added=added_count)
+                               + self.duplicates_hint())
         else:
-            self.label.setText("Keine Dateien geladen.")
+            self.label.setText(self.text("no_files"))
     
     def remove_selected_files(self):
         selected_items = self.file_list.selectedItems()
         if not selected_items:
-            self.label.setText("Keine Datei zum Entfernen ausgewählt.")
+            self.label.setText(self.text("nothing_selected"))
             return
         
         for item in selected_items:
-            file_path = item.text()
+            file_path = item.data(Qt.UserRole)
             if file_path in self.file_paths:
                 self.file_paths.remove(file_path)
             self.file_list.takeItem(self.file_list.row(item))
         
         if self.file_paths:
-            self.label.setText(f"{len(self.file_paths)} Datei(en) verbleiben.")
+            self.label.setText(self.text("files_remaining", count=len(self.file_paths)))
         else:
-            self.label.setText("Keine Dateien geladen.")
+            self.label.setText(self.text("no_files"))
+            self.set_parse_pending(False)
+        self.update_base_file_combo()
+        self.update_status(self.text("status_files_removed", count=len(selected_items)))
     
-    def process_all_files(self):
+    def process_all_files(self, export_format="csv"):
         if not self.file_paths:
-            self.label.setText("Keine Dateien geladen. Bitte erst Dateien hinzufügen.")
+            self.label.setText(self.text("no_files_to_process"))
+            return
+        if self.export_running():
+            # Zwei Läufe gleichzeitig würden in dieselbe Zieldatei schreiben
+            self.label.setText(self.text("export_running"))
+            return
+        with_transforms = self.config.get("confirm_export_transforms", False)
+        if not (self.config.get("strict_mode", False) or self.config.get("segment_targets") or with_transforms):
+            self.start_export(export_format, False)
+            return
+        self.precheck_worker = PrecheckWorker(list(self.file_paths), self.label_dict,
+                                              copy.deepcopy(self.parse_options), self.language, with_transforms, self)
+        self.precheck_worker.checks_finished.connect(
+            lambda checks: self.prechecks_finished(checks, export_format, with_transforms))
+        self.set_export_running(True)
+        self.label.setText(self.text("export_checking"))
+        self.precheck_worker.start()
+    
+    def export_running(self):
+        return self.export_worker is not None or self.precheck_worker is not None
+    
+    def prechecks_finished(self, checks, export_format, with_transforms):
+        self.precheck_worker = None
+        self.set_export_running(False)
+        if checks is None:
+            self.close_after_export = False
+            self.label.setText(self.text("export_cancelled", count=0))
+            return
+        strict = self.strict_mode_for_export(checks)
+        if (strict is None or not self.confirm_segment_targets(checks)
+                or (with_transforms and not self.confirm_export_transforms(checks))):
+            # Abgebrochen; ein beim Schließen gewähltes "Verarbeiten und beenden" lässt das Fenster offen
+            self.close_after_export = False
+            return
+        self.start_export(export_format, strict)
+    
+    def start_export(self, export_format, strict):
+        self.progress_bar.setVisible(True)
+        self.progress_bar.setMinimum(0)
+        self.progress_bar.setMaximum(len(self.file_paths) * 100)
+        self.progress_bar.setValue(0)
+        self.file_errors = {}
+        log_info(f"Verarbeitung von {len(self.file_paths)} Datei(en) nach {self.output_dir} gestartet.")
+        settings = self.export_settings(export_format, strict, self.append_file)
+        self.export_worker = ExportWorker(list(self.file_paths), settings, self)
+        self.export_worker.file_progress.connect(self.show_export_progress)
+        self.export_worker.file_done.connect(self.export_file_done)
+        self.export_worker.export_finished.connect(self.export_finished)
+        self.set_export_running(True)
+        self.export_worker.start()
+    
+    def export_settings(self, export_format, strict, append_file):
+        # Argumente für process_single_file aus der aktuellen Konfiguration
+        if append_file:
+            # Beim Anhängen bestimmt die Endung der Zieldatei das Trennzeichen
+            export_format = "tsv" if append_file.lower().endswith(".tsv") else "csv"
+        if export_format == "tsv":
+            quoting = self.config.get("tsv_quoting", "keine")
+        else:
+            quoting = self.config.get("csv_quoting", "minimal")
+        return dict(output_dir=self.output_dir, label_dict=self.label_dict, csv_columns=self.csv_columns,
+                    append_file=append_file, language=self.language,
+                    write_report=self.report_checkbox.isChecked(),
+                    options=copy.deepcopy(self.parse_options),
+                    label_targets=self.config.get("label_code_targets", {}),
+                    segment_targets=self.config.get("segment_targets", []),
+                    csv_quoting=quoting,
+                    submission_id=self.config.get("submission_id", {}),
+                    decimal_separator=self.config.get("decimal_separator", ","),
+                    csv_headers=self.config.get("csv_headers", {}),
+                    fixed_widths=self.config.get("fixed_widths", {}),
+                    export_format=export_format, strict=strict,
+                    required_fields=self.config.get("required_fields", []))
+    
+    def set_export_running(self, running):
+        for button in [self.process_button, self.tsv_button]:
+            button.setEnabled(not running)
+        self.cancel_export_button.setVisible(running)
+        self.cancel_export_button.setEnabled(running)
+    
+    def cancel_export(self):
+        worker = self.export_worker or self.precheck_worker
+        if worker is not None:
+            worker.cancel()
+            self.cancel_export_button.setEnabled(False)
+            self.label.setText(self.text("export_cancelling"))
+    
+    def show_export_progress(self, file_number, written, total):
+        # Fortschritt in Hundertsteln je Datei, damit auch eine einzelne große Datei sichtbar vorankommt
+        self.progress_bar.setValue((file_number - 1) * 100 + (written * 100 // total if total else 0))
+    
+    def export_file_done(self, file_number, input_file, summary, errors):
+        if errors:
+            self.file_errors[input_file] = errors
+        self.label.setText(summary)
+        self.progress_bar.setValue(file_number * 100)
+    
+    def export_finished(self, snapshot, outcome):
+        # outcome: None nach vollständigem Lauf, "abgebrochen" oder die aufgetretene Ausnahme
+        self.export_worker = None
+        close_after_export, self.close_after_export = self.close_after_export, False
+        self.set_export_running(False)
+        self.progress_bar.setVisible(False)
+        if isinstance(outcome, Exception):
+            self.label.setText(self.text("processing_error", error=outcome))
+            return
+        self.show_errors()
+        if outcome == "abgebrochen":
+            log_info(f"Verarbeitung abgebrochen nach {len(snapshot)} Datei(en).")
+            self.label.setText(self.text("export_cancelled", count=len(snapshot)))
             return
-        
         try:
-            self.progress_bar.setVisible(True)
-            self.progress_bar.setMinimum(0)
-            self.progress_bar.setMaximum(len(self.file_paths))
-            self.progress_bar.setValue(0)
-            
-            for i, input_file in enumerate(self.file_paths, start=1):
-                summary = process_single_file(input_file, self.output_dir, self.label_dict, self.csv_columns)
-                self.label.setText(summary)
-                self.progress_bar.setValue(i)
-            
-            self.label.setText("Verarbeitung abgeschlossen. Siehe ggf. error.log für Details.")
-            self.progress_bar.setVisible(False)
-        except Exception as e:
-            self.label.setText(f"Fehler beim Verarbeiten: {e}")
+            save_export_snapshot(snapshot)
+        except OSError as e:
+            log_error(f"Stand des Exports konnte nicht gespeichert werden: {e}")
+        log_info(f"Verarbeitung beendet, {len(self.file_errors)} Datei(en) mit Fehlern oder Warnungen.")
+        empty = [path for path, errors in self.file_errors.items()
+                 if any(error["typ"] == "keine_tracks" for error in errors)]
+        if len(empty) == len(self.file_paths):
+            self.label.setText(self.text("processing_nothing_extracted"))
+        elif empty:
+            self.label.setText(self.text("processing_done") + self.text("processing_some_empty", count=len(empty)))
+        else:
+            self.label.setText(self.text("processing_done"))
+        self.run_rows = snapshot
+        self.update_status(self.text("status_exported", count=len(snapshot)))
+        severities = [error_severity(error) for errors in self.file_errors.values() for error in errors]
+        self.show_toast(self.text("toast_processed", tracks=sum(len(rows) for rows in snapshot.values()),
+                                  errors=severities.count("fehler"), warnings=severities.count("warnung")))
+        self.dirty = False
+        remove_draft()
+        if close_after_export:
+            # Beim Schließen gewählt: "Verarbeiten und beenden"
+            self.close()
+    
+    def strict_mode_for_export(self, checks):
+        # Strenger Modus: offene Probleme vorher zeigen. Liefert, ob dieser Lauf streng exportiert,
+        # oder None zum Abbrechen; "Trotzdem exportieren" schreibt wissentlich einen Teilexport.
+        # checks: Ergebnisse von PrecheckWorker
+        if not self.config.get("strict_mode", False):
+            return False
+        lines = []
+        for input_file, problem, result, _ in checks:
+            if problem:
+                lines.append(problem)
+                continue
+            lines += [self.text("strict_line", file=os.path.basename(input_file), problem=self.error_message(error))
+                      for error in strict_problems(result, result.tracks, self.config.get("required_fields", []),
+                                                   self.parse_options, self.language)]
+        if not lines:
+            return True
+        box = QMessageBox(QMessageBox.Warning, self.text("strict_title"),
+                          self.text("strict_text", count=len(lines)), parent=self)
+        box.setDetailedText("\n".join(lines))
+        override_button = box.addButton(self.text("strict_override"), QMessageBox.DestructiveRole)
+        cancel_button = box.addButton(self.text("cancel"), QMessageBox.RejectRole)
+        box.setDefaultButton(cancel_button)
+        box.exec_()
+        return False if box.clickedButton() == override_button else None
+    
+    def confirm_segment_targets(self, checks):
+        # Abschnitte, deren Dauer vom Ziel abweicht, vor dem Schreiben zeigen und nicht erst in der Zusammenfassung
+        segments = self.config.get("segment_targets", [])
+        if not segments:
+            return True
+        lines = []
+        for input_file, _, result, _ in checks:
+            if result is None:
+                continue
+            lines += [self.text("segment_mismatch_line", file=os.path.basename(input_file), start=start, end=end,
+                                actual=format_seconds(actual), target=format_seconds(target))
+                      for start, end, actual, target, ok in check_segment_targets(result.tracks, segments) if not ok]
+        if not lines:
+            return True
+        box = QMessageBox(QMessageBox.Warning, self.text("segment_mismatch_title"),
+                          self.text("segment_mismatch_text", count=len(lines)), QMessageBox.Ok | QMessageBox.Cancel, self)
+        box.setDetailedText("\n".join(lines))
+        return box.exec_() == QMessageBox.Ok
+    
+    def confirm_export_transforms(self, checks):
+        # Vor dem Schreiben zeigen, was an Schreibweise, Leerzeichen und Dauern geändert wird
+        transforms = [transform for _, _, _, file_transforms in checks for transform in file_transforms]
+        if not transforms:
+            return True
+        counts = {kind: sum(1 for t in transforms if t[0] == kind) for kind in TRANSFORM_KINDS}
+        box = QMessageBox(QMessageBox.Information, self.text("transforms_title"),
+                          self.text("transforms_text", **counts), QMessageBox.Ok | QMessageBox.Cancel, self)
+        box.setDetailedText("\n".join(f"{self.text('transform_' + kind)}: {filename}: '{before}' -> '{after}'"
+                                      for kind, filename, before, after in transforms))
+        return box.exec_() == QMessageBox.Ok
+    
+    def error_message(self, error):
+        # Meldungen auf Dateiebene sind bereits vollständige Sätze
+        if error_severity(error) == "fehler":
+            return error["inhalt"]
+        return f"{self.text('error_type_' + error['typ'])}: {error['inhalt']}"
+    
+    def show_errors(self):
+        # Fehlertabelle füllen; Dateien mit Fehlern rot, mit nur Warnungen orange markieren
+        entries = [(path, error) for path, errors in self.file_errors.items() for error in errors]
+        self.error_table.setSortingEnabled(False)  # Sonst sortiert Qt schon während des Füllens um
+        self.error_table.setRowCount(len(entries))
+        for r, (path, error) in enumerate(entries):
+            severity = error_severity(error)
+            line = QTableWidgetItem()
+            if error["zeile"] is not None:
+                line.setData(Qt.DisplayRole, error["zeile"])  # Als Zahl, damit Zeile 10 nach Zeile 9 kommt
+            cells = [QTableWidgetItem(self.text("severity_" + severity)), QTableWidgetItem(self.display_path(path)),
+                     line, QTableWidgetItem(self.error_message(error))]
+            for c, cell in enumerate(cells):
+                cell.setData(Qt.UserRole, path)
+                cell.setData(Qt.UserRole + 1, severity)
+                cell.setForeground(QBrush(QColor(SEVERITY_COLORS[severity])))
+                self.error_table.setItem(r, c, cell)
+        self.error_table.setSortingEnabled(True)
+        self.filter_errors()
+        self.error_table.setVisible(bool(entries))
+        self.error_filter_combo.setVisible(bool(entries))
+        self.export_errors_button.setVisible(bool(entries))
+        for row in range(self.file_list.count()):
+            item = self.file_list.item(row)
+            severities = {error_severity(error) for error in self.file_errors.get(item.data(Qt.UserRole), [])}
+            if severities:
+                color = QColor(SEVERITY_COLORS["fehler" if "fehler" in severities else "warnung"])
+            else:
+                color = self.palette().text().color()
+            item.setForeground(QBrush(color))
+    
+    def filter_errors(self, *args):
+        # Nach dem Sortieren erneut aufgerufen, da die Zeilen dann an anderer Stelle stehen
+        wanted = ERROR_FILTERS[max(self.error_filter_combo.currentIndex(), 0)]
+        for r in range(self.error_table.rowCount()):
+            severity = self.error_table.item(r, 0).data(Qt.UserRole + 1)
+            self.error_table.setRowHidden(r, wanted != "alle" and severity != wanted)
+    
+    def export_aggregate(self):
+        # Summen je Labelcode für die GEMA-Meldung, unabhängig von den einzelnen CSV-Dateien
+        if not self.file_paths:
+            self.label.setText(self.text("no_files_to_process"))
+            return
+        default_name = os.path.join(self.output_dir, datetime.now().strftime("labelcodes_%Y%m%d_%H%M%S.csv"))
+        csv_file = self.save_file_name(self.text("aggregate_button"), default_name, ["CSV Files (*.csv)"])
+        if not csv_file:
+            return
+        track_dicts = []
+        skipped = 0
+        for input_file in self.file_paths:
+            problem = check_input_file(input_file, self.language)
+            try:
+                result = None if problem else parse_text_file(input_file, self.label_dict, self.parse_options)
+            except Exception as e:
+                problem = str(e)
+            if problem or result.format_problem:
+                log_error(f"Aggregat: {input_file} übersprungen ({problem or result.format_problem})")
+                skipped += 1
+                continue
+            track_dicts.append(result.tracks)
+        aggregate = label_code_aggregate(track_dicts)
+        try:
+            write_label_aggregate(csv_file, aggregate, self.language)
+        except OSError as e:
+            self.label.setText(self.text("processing_error", error=e))
+            log_error("Exception: " + traceback.format_exc())
+            return
+        message = self.text("aggregate_exported", codes=sum(1 for code in aggregate if code), path=csv_file)
+        self.label.setText(message + (self.text("aggregate_skipped", count=skipped) if skipped else ""))
+        self.update_status(self.text("status_aggregate_exported"))
+    
+    def export_template(self):
+        # Funktioniert auch ohne geladene Dateien, es wird nur die Kopfzeile geschrieben
+        default_name = os.path.join(self.output_dir, "vorlage.csv")
+        output_file = self.save_file_name(self.text("template_button"), default_name,
+                                          ["CSV Files (*.csv)", "TSV Files (*.tsv)"])
+        if not output_file:
+            return
+        headers = self.config.get("csv_headers", {})
+        tsv = output_file.lower().endswith(".tsv")
+        try:
+            write_template(output_file, [headers.get(c) or c for c in self.csv_columns],
+                           self.config.get("tsv_quoting", "keine") if tsv else self.config.get("csv_quoting", "minimal"))
+        except OSError as e:
+            self.label.setText(self.text("processing_error", error=e))
             log_error("Exception: " + traceback.format_exc())
-            self.progress_bar.setVisible(False)
+            return
+        self.label.setText(self.text("template_exported", path=output_file))
+        self.update_status(self.text("template_exported", path=output_file))
+    
+    def save_file_name(self, title, default_name, filters, **kwargs):
+        # Endung des gewählten Filters ergänzen, damit keine Dateien ohne oder mit falscher Endung entstehen
+        path, selected = QFileDialog.getSaveFileName(self, title, default_name, ";;".join(filters), **kwargs)
+        if not path:
+            return path
+        extensions = [re.search(r'\*(\.\w+)', f).group(1) for f in [selected] + filters if re.search(r'\*\.\w+', f)]
+        return ensure_extension(path, list(dict.fromkeys(extensions)))
+    
+    def copy_for_portal(self):
+        # Alle Dateien als ein Block für das GEMA-Onlineportal, Positionen laufen über die Dateien weiter
+        if not self.file_paths:
+            self.label.setText(self.text("no_files_to_process"))
+            return
+        rows = []
+        skipped = 0
+        for input_file in self.file_paths:
+            problem = check_input_file(input_file, self.language)
+            try:
+                result = None if problem else parse_text_file(input_file, self.label_dict, self.parse_options)
+            except Exception as e:
+                problem = str(e)
+            if problem or result.format_problem:
+                log_error(f"Portal: {input_file} übersprungen ({problem or result.format_problem})")
+                skipped += 1
+                continue
+            rows += track_rows(result, self.csv_columns, self.parse_options, self.config.get("submission_id", {}),
+                               self.config.get("decimal_separator", ","), first_position=len(rows) + 1)
+        headers = self.config.get("csv_headers", {})
+        QApplication.clipboard().setText(portal_text(rows, self.config.get("gema_portal", {}),
+                                                     [headers.get(c) or c for c in self.csv_columns]))
+        self.label.setText(self.text("portal_copied", count=len(rows), skipped=skipped))
+        self.show_toast(self.text("portal_copied", count=len(rows), skipped=skipped))
+    
+    def compare_with_last_export(self):
+        snapshot = load_export_snapshot()
+        if not snapshot:
+            self.label.setText(self.text("compare_no_snapshot"))
+            return
+        entries = []
+        for input_file in self.file_paths:
+            if check_input_file(input_file, self.language):
+                continue
+            try:
+                result = parse_text_file(input_file, self.label_dict, self.parse_options)
+            except Exception as e:
+                log_error(f"Vergleich für {input_file} nicht möglich: {e}")
+                continue
+            if result.format_problem:
+                continue
+            current = snapshot_rows(result.tracks, self.parse_options)
+            for status, index, fields in compare_snapshots(snapshot.get(input_file, {}), current):
+                entries.append((input_file, status, index, fields))
+        if not entries:
+            self.label.setText(self.text("compare_no_changes"))
+            return
+        ExportDiffDialog(entries, self.language, self).exec_()
+    
+    def export_error_list(self):
+        default_name = os.path.join(self.output_dir, datetime.now().strftime("fehler_%Y%m%d_%H%M%S.csv"))
+        error_file = self.save_file_name(self.text("export_errors_button"), default_name,
+                                         ["CSV Files (*.csv)", "Text Files (*.txt)"])
+        if not error_file:
+            return
+        try:
+            export_errors(error_file, self.file_errors)
+            self.label.setText(self.text("errors_exported", path=error_file))
+            self.update_status(self.text("status_errors_exported"))
+        except OSError as e:
+            self.label.setText(self.text("processing_error", error=e))
+            log_error("Exception: " + traceback.format_exc())
+    
+    def show_file_of_error(self, row, column=0):
+        path = self.error_table.item(row, column).data(Qt.UserRole)
+        for row in range(self.file_list.count()):
+            item = self.file_list.item(row)
+            if item.data(Qt.UserRole) == path:
+                self.file_list.setCurrentItem(item)
+                self.file_list.scrollToItem(item)
+                break
+    
+    def autosave_draft(self):
+        if not self.dirty or not self.file_paths:
+            return
+        try:
+            save_draft({"zeitstempel": datetime.now().isoformat(timespec='seconds'),
+                        "dateien": self.file_paths,
+                        "korrekturen": self.parse_options.overrides})
+        except OSError as e:
+            log_error(f"Entwurf konnte nicht gespeichert werden: {e}")
+    
+    def offer_draft_recovery(self):
+        draft = load_draft()
+        if not draft or not draft.get("dateien"):
+            return
+        answer = QMessageBox.question(self, self.text("draft_title"),
+                                      self.text("draft_text", count=len(draft["dateien"]),
+                                                timestamp=draft.get("zeitstempel", "?")),
+                                      QMessageBox.Yes | QMessageBox.No)
+        if answer == QMessageBox.Yes:
+            self.parse_options.overrides.update(draft.get("korrekturen", {}))
+            self.add_files(draft["dateien"])
+            self.label.setText(self.text("draft_recovered", count=len(self.file_paths)))
+        else:
+            remove_draft()
+    
+    def stop_running_export(self):
+        # Nicht mitten in eine Datei hinein beenden: abbrechen und die angefangene Ausgabe entfernen lassen
+        for worker in [self.precheck_worker, self.export_worker]:
+            if worker is not None:
+                worker.cancel()
+                worker.wait()
+
+    def confirm_exit_during_export(self):
+        # Ein abgebrochener Export entfernt seine angefangene Ausgabe, daher nie ungefragt abbrechen.
+        # Liefert True, wenn sofort beendet werden soll
+        box = QMessageBox(QMessageBox.Question, self.text("confirm_exit_title"),
+                          self.text("confirm_exit_export_text"), parent=self)
+        wait_button = box.addButton(self.text("confirm_exit_after_export"), QMessageBox.AcceptRole)
+        stop_button = box.addButton(self.text("confirm_exit_cancel_export"), QMessageBox.DestructiveRole)
+        box.addButton(self.text("cancel"), QMessageBox.RejectRole)
+        box.setDefaultButton(wait_button)
+        box.exec_()
+        clicked = box.clickedButton()
+        if clicked == wait_button:
+            # export_finished schließt das Fenster, wenn der Export fertig ist
+            self.close_after_export = True
+        if clicked != stop_button:
+            return False
+        self.stop_running_export()
+        return True
+
+    def closeEvent(self, event):
+        if self.export_running():
+            if not self.confirm_exit_during_export():
+                event.ignore()
+                return
+            if self.dirty:
+                # Der Entwurf bleibt, damit die nicht verarbeiteten Dateien beim nächsten Start angeboten werden
+                event.accept()
+                return
+        if not self.dirty or not self.file_paths:
+            remove_draft()
+            event.accept()
+            return
+        box = QMessageBox(QMessageBox.Question, self.text("confirm_exit_title"),
+                          self.text("confirm_exit_text", count=len(self.file_paths)), parent=self)
+        process_button = box.addButton(self.text("confirm_exit_process"), QMessageBox.AcceptRole)
+        box.addButton(self.text("confirm_exit_discard"), QMessageBox.DestructiveRole)
+        cancel_button = box.addButton(self.text("cancel"), QMessageBox.RejectRole)
+        box.exec_()
+        clicked = box.clickedButton()
+        if clicked == cancel_button:
+            event.ignore()
+            return
+        if clicked == process_button:
+            # Der Export läuft im Hintergrund; export_finished schließt das Fenster, wenn er fertig ist
+            self.close_after_export = True
+            self.process_all_files()
+            # Ohne gestarteten Export bleibt das Fenster einfach offen; wird eine Rückfrage vor dem Export
+            # abgebrochen, setzt prechecks_finished das zurück
+            self.close_after_export = self.export_running()
+            event.ignore()
+            return
+        remove_draft()
+        event.accept()
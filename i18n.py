@@ -0,0 +1,560 @@
+import locale
+from enum import Enum
+
+class Language(Enum):
+    DE = "de"
+    EN = "en"
+
+# Sichtbare Texte der Oberfläche, je Schlüssel eine Übersetzung pro Sprache
+TRANSLATIONS = {
+    "window_title": {Language.DE: "Track Parser", Language.EN: "Track Parser"},
+    "output_button": {Language.DE: "Ausgabeort wählen", Language.EN: "Choose output folder"},
+    "output_button_tip": {Language.DE: "Wähle den Ordner für die CSV-Ausgabe.",
+                          Language.EN: "Choose the folder for the CSV output."},
+    "reload_button": {Language.DE: "Labelcodes neu laden", Language.EN: "Reload label codes"},
+    "reassign_button": {Language.DE: "Labelcodes neu zuordnen", Language.EN: "Reassign label codes"},
+    "reassign_button_tip": {Language.DE: "Ordnet allen geladenen Zeilen die Labelcodes neu zu, ohne die Datei neu zu laden. "
+                                          "Von Hand gesetzte Labelcodes bleiben erhalten.",
+                            Language.EN: "Reassigns label codes to all loaded rows without reloading the file. "
+                                         "Manually set label codes are kept."},
+    "reload_button_tip": {Language.DE: "Lade die Labelcodes neu.", Language.EN: "Reload the label codes."},
+    "file_select_button": {Language.DE: "Datei auswählen", Language.EN: "Select file"},
+    "file_select_button_tip": {Language.DE: "Wähle .txt- oder .cue-Dateien aus.",
+                               Language.EN: "Select .txt or .cue files."},
+    "language_button": {Language.DE: "English", Language.EN: "Deutsch"},
+    "language_button_tip": {Language.DE: "Switch the interface to English.",
+                            Language.EN: "Oberfläche auf Deutsch umstellen."},
+    "drop_hint": {Language.DE: "Ziehe Dateien oder Ordner hierher oder nutze die Buttons oben.",
+                  Language.EN: "Drag files or folders here or use the buttons above."},
+    "encoding_not_utf8": {Language.DE: "kein UTF-8", Language.EN: "not UTF-8"},
+    "line_endings_mixed": {Language.DE: "gemischt ({endings})", Language.EN: "mixed ({endings})"},
+    "file_list_tip": {Language.DE: "Geladene Dateien. Reihenfolge per Drag & Drop oder Strg+Pfeil ändern, "
+                                   "die erste Datei gibt den Namen der Sammel-CSV vor.",
+                      Language.EN: "Loaded files. Change the order by drag and drop or Ctrl+arrow; "
+                                   "the first file suggests the name of the combined CSV."},
+    "remove_button": {Language.DE: "Entfernen", Language.EN: "Remove"},
+    "remove_button_tip": {Language.DE: "Ausgewählte Dateien entfernen.", Language.EN: "Remove selected files."},
+    "process_button": {Language.DE: "Los", Language.EN: "Go"},
+    "process_button_tip": {Language.DE: "Verarbeitung starten.", Language.EN: "Start processing."},
+    "strict_title": {Language.DE: "Strenger Modus: Export verweigert", Language.EN: "Strict mode: export refused"},
+    "strict_text": {Language.DE: "{count} Problem(e) müssen vor dem Export behoben werden (siehe Details). "
+                                 "Wissentlich einen unvollständigen Export schreiben?",
+                    Language.EN: "{count} problem(s) must be fixed before exporting (see details). "
+                                 "Knowingly write an incomplete export?"},
+    "strict_override": {Language.DE: "Trotzdem exportieren", Language.EN: "Export anyway"},
+    "strict_line": {Language.DE: "{file}: {problem}", Language.EN: "{file}: {problem}"},
+    "strict_refused": {Language.DE: "{path}: Strenger Modus, nichts geschrieben – {count} Problem(e) beheben.",
+                       Language.EN: "{path}: strict mode, nothing written – fix {count} problem(s)."},
+    "strict_missing_fields": {Language.DE: "{track}: Pflichtfeld leer: {fields}",
+                              Language.EN: "{track}: required field empty: {fields}"},
+    "error_type_pflichtfeld": {Language.DE: "Pflichtfeld leer", Language.EN: "Required field empty"},
+    "segment_mismatch_title": {Language.DE: "Abschnitte weichen ab", Language.EN: "Segments differ"},
+    "segment_mismatch_text": {Language.DE: "{count} Abschnitt(e) haben nicht die eingestellte Dauer (siehe Details). "
+                                           "Trotzdem exportieren?",
+                              Language.EN: "{count} segment(s) do not have the configured duration (see details). "
+                                           "Export anyway?"},
+    "segment_mismatch_line": {Language.DE: "{file}, Index {start}-{end}: {actual} statt {target}",
+                              Language.EN: "{file}, index {start}-{end}: {actual} instead of {target}"},
+    "transforms_title": {Language.DE: "Änderungen beim Export", Language.EN: "Changes on export"},
+    "transforms_text": {Language.DE: "Beim Export werden Werte aus den Textdateien angepasst:\n"
+                                     "{klein} Titel/Künstler in anderer Schreibweise, {bereinigt} Felder bereinigt, "
+                                     "{gerundet} Dauern gerundet.\nDetails zeigen jede Änderung. Exportieren?",
+                        Language.EN: "Exporting adjusts values from the text files:\n"
+                                     "{klein} titles/artists with different casing, {bereinigt} fields cleaned up, "
+                                     "{gerundet} durations rounded.\nDetails list every change. Export?"},
+    "transform_klein": {Language.DE: "Schreibweise", Language.EN: "Casing"},
+    "transform_bereinigt": {Language.DE: "Bereinigt", Language.EN: "Cleaned up"},
+    "transform_gerundet": {Language.DE: "Gerundet", Language.EN: "Rounded"},
+    "template_button": {Language.DE: "Leere Vorlage", Language.EN: "Empty template"},
+    "template_button_tip": {Language.DE: "CSV oder TSV nur mit der Kopfzeile aus den eingestellten Spalten "
+                                         "zum Ausfüllen von Hand.",
+                            Language.EN: "CSV or TSV with only the header row of the configured columns, "
+                                         "to fill in by hand."},
+    "template_exported": {Language.DE: "Vorlage gespeichert: {path}", Language.EN: "Template saved: {path}"},
+    "rules_button": {Language.DE: "Regeln", Language.EN: "Rules"},
+    "rules_button_tip": {Language.DE: "Suchen und Ersetzen in Index, Titel und Künstler nach dem Zerlegen, "
+                                      "z.B. ein Präfix entfernen oder einen Künstlernamen umschreiben.",
+                         Language.EN: "Find and replace in index, title and artist after parsing, "
+                                      "e.g. strip a prefix or map one artist name to another."},
+    "rules_headers": {Language.DE: ["Feld", "Suchen", "Ersetzen", "Regulärer Ausdruck"],
+                      Language.EN: ["Field", "Find", "Replace", "Regular expression"]},
+    "rules_field_alle": {Language.DE: "Alle", Language.EN: "All"},
+    "rules_field_index": {Language.DE: "Index", Language.EN: "Index"},
+    "rules_field_titel": {Language.DE: "Titel", Language.EN: "Title"},
+    "rules_field_kuenstler": {Language.DE: "Künstler", Language.EN: "Artist"},
+    "rules_sample_placeholder": {Language.DE: "Beispiel-Dateiname, z.B. KPM1234_05_SUMMER_RAIN_john_smith.wav",
+                                 Language.EN: "Sample filename, e.g. KPM1234_05_SUMMER_RAIN_john_smith.wav"},
+    "rules_test": {Language.DE: "Testen", Language.EN: "Test"},
+    "rules_result": {Language.DE: "Index: {index}  Titel: {title}  Künstler: {artist}",
+                     Language.EN: "Index: {index}  Title: {title}  Artist: {artist}"},
+    "rules_invalid": {Language.DE: "Regel {row}: {error}", Language.EN: "Rule {row}: {error}"},
+    "rules_saved": {Language.DE: "{count} Regel(n) gespeichert.", Language.EN: "{count} rule(s) saved."},
+    "portal_button": {Language.DE: "Für GEMA-Portal kopieren", Language.EN: "Copy for GEMA portal"},
+    "portal_button_tip": {Language.DE: "Alle Tracks mit den CSV-Spalten so in die Zwischenablage kopieren, wie das "
+                                       "GEMA-Onlineportal sie zum Einfügen erwartet (Einstellung \"gema_portal\").",
+                          Language.EN: "Copy all tracks with the CSV columns to the clipboard in the layout the GEMA "
+                                       "online portal expects for pasting (setting \"gema_portal\")."},
+    "portal_copied": {Language.DE: "{count} Zeilen für das GEMA-Portal kopiert, {skipped} Datei(en) übersprungen",
+                      Language.EN: "{count} rows copied for the GEMA portal, {skipped} file(s) skipped"},
+    "settings_button": {Language.DE: "Einstellungen", Language.EN: "Settings"},
+    "settings_button_tip": {Language.DE: "Alle Einstellungen in einem Fenster bearbeiten.",
+                            Language.EN: "Edit all settings in one window."},
+    "settings_apply": {Language.DE: "Übernehmen", Language.EN: "Apply"},
+    "settings_saved": {Language.DE: "Einstellungen gespeichert.", Language.EN: "Settings saved."},
+    "settings_invalid_regex": {Language.DE: "{setting}: ungültiges Muster '{pattern}' ({error})",
+                               Language.EN: "{setting}: invalid pattern '{pattern}' ({error})"},
+    "settings_invalid_json": {Language.DE: "{setting}: ungültiger Wert ({error})",
+                              Language.EN: "{setting}: invalid value ({error})"},
+    "settings_json_type_dict": {Language.DE: "JSON-Objekt {...} erwartet", Language.EN: "expected a JSON object {...}"},
+    "settings_json_type_list": {Language.DE: "JSON-Liste [...] erwartet", Language.EN: "expected a JSON list [...]"},
+    "settings_no_columns": {Language.DE: "Mindestens eine Spalte angeben.", Language.EN: "Enter at least one column."},
+    "settings_tab_einlesen": {Language.DE: "Einlesen", Language.EN: "Reading"},
+    "settings_tab_abgleich": {Language.DE: "Abgleich", Language.EN: "Matching"},
+    "settings_tab_export": {Language.DE: "Export", Language.EN: "Export"},
+    "settings_tab_allgemein": {Language.DE: "Allgemein", Language.EN: "General"},
+    "settings_entry_delimiter": {Language.DE: "Trennzeichen einzeiliger Einträge (leer = erkennen)",
+                                 Language.EN: "Delimiter of single-line entries (empty = detect)"},
+    "settings_separators": {Language.DE: "Worttrenner im Dateinamen", Language.EN: "Word separators in file names"},
+    "settings_header_line": {Language.DE: "Kopfzeile", Language.EN: "Header line"},
+    "settings_index_token_count": {Language.DE: "Index-Tokens (0 = automatisch)",
+                                   Language.EN: "Index tokens (0 = automatic)"},
+    "settings_filename_patterns": {Language.DE: "Dateinamen-Muster (reguläre Ausdrücke)",
+                                   Language.EN: "File name patterns (regular expressions)"},
+    "settings_filename_duration_patterns": {Language.DE: "Dauer im Dateinamen (reguläre Ausdrücke)",
+                                            Language.EN: "Duration in file name (regular expressions)"},
+    "settings_strip_chars": {Language.DE: "Zeichen am Rand entfernen", Language.EN: "Strip characters at the edges"},
+    "settings_default_duration": {Language.DE: "Platzhalter-Dauer", Language.EN: "Placeholder duration"},
+    "settings_artist_separator": {Language.DE: "Wörter des Künstlers verbinden mit",
+                                  Language.EN: "Join artist words with"},
+    "settings_bracket_metadata": {Language.DE: "[Genre] und (Jahr) auslesen", Language.EN: "Read [genre] and (year)"},
+    "settings_ignore_index_leading_zeros": {Language.DE: "Führende Nullen im Index ignorieren",
+                                            Language.EN: "Ignore leading zeros in index"},
+    "settings_merge_durations": {Language.DE: "Dauern gleicher Tracks addieren",
+                                 Language.EN: "Add up durations of identical tracks"},
+    "settings_normalize_case": {Language.DE: "Title-Case für Titel und Künstler",
+                                Language.EN: "Title case for title and artist"},
+    "settings_text_only_tracks": {Language.DE: "Tracks ohne Audiodatei", Language.EN: "Tracks without audio file"},
+    "settings_check_index_sequence": {Language.DE: "Index-Folge prüfen", Language.EN: "Check index sequence"},
+    "settings_label_code_targets": {Language.DE: "Zielsummen je Labelcode (JSON)",
+                                    Language.EN: "Target totals per label code (JSON)"},
+    "settings_segment_targets": {Language.DE: "Zieldauern je Abschnitt (JSON)",
+                                 Language.EN: "Target durations per segment (JSON)"},
+    "settings_original_case_columns": {Language.DE: "Spalten in Originalschreibweise",
+                                       Language.EN: "Columns in original casing"},
+    "settings_csv_columns": {Language.DE: "Spalten (eine je Zeile)", Language.EN: "Columns (one per line)"},
+    "settings_csv_quoting": {Language.DE: "Anführungszeichen (CSV)", Language.EN: "Quoting (CSV)"},
+    "settings_tsv_quoting": {Language.DE: "Anführungszeichen (TSV)", Language.EN: "Quoting (TSV)"},
+    "settings_decimal_separator": {Language.DE: "Dezimaltrennzeichen", Language.EN: "Decimal separator"},
+    "settings_index_numbers": {Language.DE: "Zahlen im Index", Language.EN: "Numbers in index"},
+    "settings_index_number_width": {Language.DE: "Stellen beim Auffüllen", Language.EN: "Digits when padding"},
+    "settings_confirm_export_transforms": {Language.DE: "Änderungen vor dem Export zeigen",
+                                           Language.EN: "Show changes before export"},
+    "settings_strict_mode": {Language.DE: "Strenger Modus", Language.EN: "Strict mode"},
+    "settings_required_fields": {Language.DE: "Pflichtspalten (strenger Modus)",
+                                 Language.EN: "Required columns (strict mode)"},
+    "settings_language": {Language.DE: "Sprache", Language.EN: "Language"},
+    "settings_file_log_level": {Language.DE: "Protokollstufe", Language.EN: "Log level"},
+    "settings_max_file_count": {Language.DE: "Höchstzahl Dateien", Language.EN: "Maximum number of files"},
+    "settings_autosave_interval": {Language.DE: "Entwurf speichern alle (Sekunden)",
+                                   Language.EN: "Save draft every (seconds)"},
+    "settings_auto_parse": {Language.DE: "Automatisch parsen", Language.EN: "Parse automatically"},
+    "settings_compact_view": {Language.DE: "Kompakte Ansicht", Language.EN: "Compact view"},
+    "settings_submission_id": {Language.DE: "Meldungsnummer (JSON)", Language.EN: "Submission ID (JSON)"},
+    "settings_fixed_widths": {Language.DE: "Feste Spaltenbreiten (JSON)", Language.EN: "Fixed column widths (JSON)"},
+    "settings_gema_portal": {Language.DE: "GEMA-Portal-Format (JSON)", Language.EN: "GEMA portal format (JSON)"},
+    "settings_path_display": {Language.DE: "Pfade in der Dateiliste", Language.EN: "Paths in the file list"},
+    "settings_path_display_base": {Language.DE: "Basisordner für relative Pfade",
+                                   Language.EN: "Base folder for relative paths"},
+    "settings_rename_template": {Language.DE: "Vorlage für Umbenennen", Language.EN: "Rename template"},
+    "settings_musicbrainz_enabled": {Language.DE: "MusicBrainz-Abgleich", Language.EN: "MusicBrainz lookup"},
+    "reset_settings_button": {Language.DE: "Einstellungen zurücksetzen", Language.EN: "Reset settings"},
+    "reset_settings_button_tip": {Language.DE: "Alle Einstellungen auf die Standardwerte setzen und neu parsen.",
+                                  Language.EN: "Restore all settings to their defaults and parse again."},
+    "reset_settings_confirm": {Language.DE: "Alle Einstellungen auf die Standardwerte zurücksetzen? Sprache, zuletzt "
+                                            "verwendete Pfade, Labelcode-Dateien und Export-Ordner bleiben erhalten.",
+                               Language.EN: "Restore all settings to their defaults? Language, recent paths, label "
+                                            "code files and export folder are kept."},
+    "settings_reset": {Language.DE: "Einstellungen zurückgesetzt", Language.EN: "Settings reset"},
+    "tsv_button": {Language.DE: "TSV exportieren", Language.EN: "Export TSV"},
+    "tsv_button_tip": {Language.DE: "Wie \"Los\", aber tabulatorgetrennt ohne Anführungszeichen als .tsv.",
+                       Language.EN: "Like \"Go\", but tab-separated without quotes as .tsv."},
+    "debug_button": {Language.DE: "Debug-Ansicht", Language.EN: "Debug view"},
+    "debug_button_tip": {Language.DE: "Zeigt, wie die Dateinamen der ausgewählten Datei zerlegt werden.",
+                         Language.EN: "Shows how the filenames of the selected file are split."},
+    "append_checkbox": {Language.DE: "An CSV anhängen", Language.EN: "Append to CSV"},
+    "append_checkbox_tip": {Language.DE: "Alle Tracks an eine bestehende CSV anhängen statt neue Dateien zu schreiben.",
+                            Language.EN: "Append all tracks to an existing CSV instead of writing new files."},
+    "report_checkbox": {Language.DE: "JSON-Bericht", Language.EN: "JSON report"},
+    "report_checkbox_tip": {Language.DE: "Zusätzlich einen maschinenlesbaren Bericht (<name>_report.json) schreiben.",
+                            Language.EN: "Also write a machine-readable report (<name>_report.json)."},
+    "labelcodes_button": {Language.DE: "Labelcodes verwalten", Language.EN: "Manage label codes"},
+    "labelcodes_button_tip": {Language.DE: "Labelcodes ansehen, hinzufügen, bearbeiten und löschen.",
+                              Language.EN: "View, add, edit and delete label codes."},
+    "labelcodes_headers": {Language.DE: ["Label", "Code"], Language.EN: ["Label", "Code"]},
+    "labelcodes_add": {Language.DE: "Hinzufügen", Language.EN: "Add"},
+    "labelcodes_delete": {Language.DE: "Löschen", Language.EN: "Delete"},
+    "headers_button": {Language.DE: "Spaltenüberschriften", Language.EN: "Column headers"},
+    "headers_button_tip": {Language.DE: "Überschriften der CSV-Spalten an die Importvorlage anpassen.",
+                           Language.EN: "Adjust the CSV column headers to the import template."},
+    "headers_table_headers": {Language.DE: ["Spalte", "Überschrift (leer = Spaltenname)"],
+                              Language.EN: ["Column", "Header (empty = column name)"]},
+    "headers_saved": {Language.DE: "Spaltenüberschriften gespeichert.", Language.EN: "Column headers saved."},
+    "labelcodes_save": {Language.DE: "Speichern", Language.EN: "Save"},
+    "cancel": {Language.DE: "Abbrechen", Language.EN: "Cancel"},
+    "labelcodes_invalid": {Language.DE: "Labelcodes wurden nicht gespeichert:", Language.EN: "Label codes were not saved:"},
+    "labelcodes_saved": {Language.DE: "Labelcodes wurden gespeichert und neu geladen.",
+                         Language.EN: "Label codes saved and reloaded."},
+    "labelcode_empty": {Language.DE: "Zeile {row}: Label und Code dürfen nicht leer sein.",
+                        Language.EN: "Row {row}: label and code must not be empty."},
+    "labelcode_duplicate": {Language.DE: "Zeile {row}: Label '{label}' ist doppelt vorhanden.",
+                            Language.EN: "Row {row}: label '{label}' is a duplicate."},
+    "pattern_button": {Language.DE: "Pfad/Muster hinzufügen", Language.EN: "Add path/pattern"},
+    "pattern_button_tip": {Language.DE: "Fügt alle .txt-Dateien hinzu, die auf das Muster passen (z.B. ./daten/*.txt).",
+                           Language.EN: "Adds all .txt files matching the pattern (e.g. ./data/*.txt)."},
+    "pattern_placeholder": {Language.DE: "Pfad oder Muster, z.B. ./daten/**/*.txt",
+                            Language.EN: "Path or pattern, e.g. ./data/**/*.txt"},
+    "pattern_no_match": {Language.DE: "Keine Treffer für: {patterns}", Language.EN: "No matches for: {patterns}"},
+    "rename_button": {Language.DE: "Dateien umbenennen", Language.EN: "Rename files"},
+    "rename_button_tip": {Language.DE: "Benennt die Audiodateien der ausgewählten Textdatei nach der Vorlage um.",
+                          Language.EN: "Renames the audio files of the selected text file using the template."},
+    "rename_no_selection": {Language.DE: "Bitte zuerst eine Textdatei auswählen.", Language.EN: "Please select a text file first."},
+    "rename_nothing": {Language.DE: "Alle Dateinamen entsprechen bereits der Vorlage.",
+                       Language.EN: "All filenames already match the template."},
+    "rename_confirm": {Language.DE: "{count} Datei(en) umbenennen? ({skipped} werden übersprungen, siehe Details)",
+                       Language.EN: "Rename {count} file(s)? ({skipped} will be skipped, see details)"},
+    "rename_done": {Language.DE: "{count} Datei(en) umbenannt.", Language.EN: "{count} file(s) renamed."},
+    "rename_problem_nicht_gefunden": {Language.DE: "nicht gefunden", Language.EN: "not found"},
+    "rename_problem_doppelter_name": {Language.DE: "Zielname doppelt", Language.EN: "duplicate target name"},
+    "rename_problem_ziel_existiert": {Language.DE: "Ziel existiert bereits", Language.EN: "target already exists"},
+    "compact_header": {Language.DE: "Index · Titel – Künstler (Dauer) [Labelcode]",
+                       Language.EN: "Index · Title – Artist (Duration) [Label code]"},
+    "parse_now_button": {Language.DE: "Jetzt parsen", Language.EN: "Parse now"},
+    "parse_now_button_tip": {Language.DE: "Die hinzugefügten Dateien jetzt auswerten.",
+                             Language.EN: "Parse the added files now."},
+    "parse_pending": {Language.DE: "{count} Datei(en) geladen. Noch nicht geparst – \"Jetzt parsen\" klicken.",
+                      Language.EN: "{count} file(s) loaded. Not parsed yet – click \"Parse now\"."},
+    "folder_import_title": {Language.DE: "Ordner importieren", Language.EN: "Import folder"},
+    "folder_import_text": {Language.DE: ("In {path} gefunden:\n"
+                                         "  Textdateien: {found}\n"
+                                         "  davon lesbar: {readable}\n"
+                                         "  davon leer oder nicht lesbar: {problems}\n"
+                                         "  davon bereits in der Liste: {known}\n"
+                                         "  Andere Dateien (ignoriert): {ignored}"),
+                           Language.EN: ("Found in {path}:\n"
+                                         "  Text files: {found}\n"
+                                         "  readable: {readable}\n"
+                                         "  empty or not readable: {problems}\n"
+                                         "  already in the list: {known}\n"
+                                         "  Other files (ignored): {ignored}")},
+    "folder_import_continue": {Language.DE: "Weiter", Language.EN: "Continue"},
+    "paste_button": {Language.DE: "Aus Zwischenablage einfügen", Language.EN: "Paste from clipboard"},
+    "paste_button_tip": {Language.DE: "Dateipfade aus der Zwischenablage hinzufügen oder eine Trackliste (Dateiname;Dauer "
+                                      "pro Zeile) direkt exportieren.",
+                         Language.EN: "Add file paths from the clipboard or export a track list (file name;duration per "
+                                      "line) directly."},
+    "paste_empty": {Language.DE: "Die Zwischenablage enthält keinen Text.", Language.EN: "The clipboard contains no text."},
+    "paste_choose_mode": {Language.DE: "Die Zwischenablage enthält {count} Zeile(n). Wie sollen sie verwendet werden?",
+                          Language.EN: "The clipboard contains {count} line(s). How should they be used?"},
+    "paste_as_paths": {Language.DE: "Als Dateipfade", Language.EN: "As file paths"},
+    "paste_as_tracks": {Language.DE: "Als Trackliste", Language.EN: "As track list"},
+    "recent_button": {Language.DE: "Zuletzt verwendet", Language.EN: "Recent"},
+    "recent_button_tip": {Language.DE: "Zuletzt geöffnete Dateien und Ordner erneut hinzufügen.",
+                          Language.EN: "Add recently opened files and folders again."},
+    "recent_empty": {Language.DE: "(keine Einträge)", Language.EN: "(no entries)"},
+    "recent_missing": {Language.DE: "{path} existiert nicht mehr und wurde aus der Liste entfernt.",
+                       Language.EN: "{path} no longer exists and was removed from the list."},
+    "duplicates_skipped": {Language.DE: "{count} Datei(en) waren bereits in der Liste und wurden übersprungen.",
+                           Language.EN: "{count} file(s) were already in the list and were skipped."},
+    "draft_title": {Language.DE: "Entwurf wiederherstellen", Language.EN: "Recover draft"},
+    "draft_text": {Language.DE: "Es gibt einen automatisch gesicherten Entwurf vom {timestamp} mit {count} Datei(en), "
+                                "der nicht verarbeitet wurde. Wiederherstellen?",
+                   Language.EN: "There is an autosaved draft from {timestamp} with {count} file(s) "
+                                "that was not processed. Recover it?"},
+    "draft_recovered": {Language.DE: "Entwurf wiederhergestellt: {count} Datei(en).",
+                        Language.EN: "Draft recovered: {count} file(s)."},
+    "track_edit_title": {Language.DE: "Korrektur: {filename}", Language.EN: "Correction: {filename}"},
+    "track_edit_field_title": {Language.DE: "Titel", Language.EN: "Title"},
+    "track_edit_field_artist": {Language.DE: "Künstler", Language.EN: "Artist"},
+    "track_edit_field_label_code": {Language.DE: "Labelcode (leer = automatisch)",
+                                    Language.EN: "Label code (empty = automatic)"},
+    "track_edit_field_duration": {Language.DE: "Dauer", Language.EN: "Duration"},
+    "track_edit_required": {Language.DE: "Pflichtfeld, darf nicht leer sein.", Language.EN: "Required, must not be empty."},
+    "track_edit_invalid_duration": {Language.DE: "Ungültige Dauer, erwartet z.B. 3:45 oder 0:03:45.",
+                                    Language.EN: "Invalid duration, expected e.g. 3:45 or 0:03:45."},
+    "track_edited": {Language.DE: "Korrektur für {filename} gespeichert.", Language.EN: "Correction for {filename} saved."},
+    "track_reset": {Language.DE: "Korrektur für {filename} entfernt.", Language.EN: "Correction for {filename} removed."},
+    "swap_button": {Language.DE: "Titel/Künstler tauschen", Language.EN: "Swap title/artist"},
+    "swap_button_tip": {Language.DE: "Titel und Künstler der markierten Zeile in der Debug-Ansicht vertauschen.",
+                        Language.EN: "Swap title and artist of the selected row in the debug view."},
+    "swap_all_button": {Language.DE: "Alle tauschen", Language.EN: "Swap all"},
+    "swap_all_button_tip": {Language.DE: "Titel und Künstler aller Zeilen der aktuellen Datei vertauschen.",
+                            Language.EN: "Swap title and artist of all rows of the current file."},
+    "find_replace_button": {Language.DE: "Suchen/Ersetzen", Language.EN: "Find/replace"},
+    "find_replace_button_tip": {Language.DE: "Einen Fehler in einem Feld aller Zeilen der aktuellen Datei auf einmal "
+                                             "korrigieren, mit Vorschau; wird wie eine Korrektur gespeichert.",
+                                Language.EN: "Fix a recurring error in one field of all rows of the current file at once, "
+                                             "with preview; saved like a correction."},
+    "find_replace_field": {Language.DE: "Feld", Language.EN: "Field"},
+    "find_replace_fields": {Language.DE: ["Index", "Titel", "Künstler", "Labelcode"],
+                            Language.EN: ["Index", "Title", "Artist", "Label code"]},
+    "find_replace_search": {Language.DE: "Suchen", Language.EN: "Find"},
+    "find_replace_replace": {Language.DE: "Ersetzen durch", Language.EN: "Replace with"},
+    "find_replace_case": {Language.DE: "Groß-/Kleinschreibung beachten", Language.EN: "Match case"},
+    "find_replace_whole_word": {Language.DE: "Nur ganze Wörter", Language.EN: "Whole words only"},
+    "find_replace_headers": {Language.DE: ["Dateiname", "Vorher", "Nachher"],
+                             Language.EN: ["File name", "Before", "After"]},
+    "find_replace_preview": {Language.DE: "{count} Zeile(n) betroffen.", Language.EN: "{count} row(s) affected."},
+    "find_replace_apply": {Language.DE: "Ersetzen", Language.EN: "Replace"},
+    "find_replace_done": {Language.DE: "{count} Zeile(n) korrigiert.", Language.EN: "{count} row(s) corrected."},
+    "swap_no_selection": {Language.DE: "Bitte in der Debug-Ansicht eine Zeile markieren.",
+                          Language.EN: "Please select a row in the debug view."},
+    "swapped": {Language.DE: "Titel und Künstler für {count} Zeile(n) getauscht.",
+                Language.EN: "Swapped title and artist for {count} row(s)."},
+    "swap_hint": {Language.DE: "{count} Zeile(n) sehen vertauscht aus (Künstler in Großbuchstaben, markiert).",
+                  Language.EN: "{count} row(s) look swapped (artist in capitals, highlighted)."},
+    "swap_hint_tip": {Language.DE: "Künstler sieht wie ein Titel aus – evtl. vertauscht.",
+                      Language.EN: "Artist looks like a title – possibly swapped."},
+    "musicbrainz_button": {Language.DE: "MusicBrainz-Abfrage", Language.EN: "MusicBrainz lookup"},
+    "musicbrainz_button_tip": {Language.DE: "Sucht Titel und Künstler der ausgewählten Zeile der Debug-Ansicht online.",
+                               Language.EN: "Looks up title and artist of the selected debug view row online."},
+    "musicbrainz_no_selection": {Language.DE: "Bitte eine Zeile in der Debug-Ansicht auswählen.",
+                                 Language.EN: "Please select a row in the debug view."},
+    "musicbrainz_running": {Language.DE: "MusicBrainz-Abfrage läuft ...", Language.EN: "MusicBrainz lookup running ..."},
+    "musicbrainz_error": {Language.DE: "MusicBrainz-Abfrage fehlgeschlagen: {error}",
+                          Language.EN: "MusicBrainz lookup failed: {error}"},
+    "musicbrainz_no_match": {Language.DE: "Kein MusicBrainz-Treffer für {filename}.",
+                             Language.EN: "No MusicBrainz match for {filename}."},
+    "musicbrainz_accept": {Language.DE: "Vorschlag für {filename}:\nTitel: {title}\nKünstler: {artist}\n\nÜbernehmen?",
+                           Language.EN: "Suggestion for {filename}:\nTitle: {title}\nArtist: {artist}\n\nAccept?"},
+    "musicbrainz_accepted": {Language.DE: "Korrektur für {filename} übernommen.",
+                             Language.EN: "Correction for {filename} accepted."},
+    "debug_headers": {Language.DE: ["Dateiname", "Tokens", "Index", "Titel", "Künstler", "Labelcode", "Dauer"],
+                      Language.EN: ["Filename", "Tokens", "Index", "Title", "Artist", "Label code", "Duration"]},
+    "tooltip_filename": {Language.DE: "Dateiname: {filename}", Language.EN: "Filename: {filename}"},
+    "tooltip_tokens": {Language.DE: "Tokens: {tokens}", Language.EN: "Tokens: {tokens}"},
+    "tooltip_cleanup": {Language.DE: "Bereinigt ({field}): \"{before}\" → \"{after}\"",
+                        Language.EN: "Cleaned ({field}): \"{before}\" → \"{after}\""},
+    "tooltip_fields": {Language.DE: "Index: {index} / Titel: {title} / Künstler: {artist}",
+                       Language.EN: "Index: {index} / Title: {title} / Artist: {artist}"},
+    "tooltip_duration": {Language.DE: "Dauer: {duration} (aus Textdatei: '{raw}')",
+                         Language.EN: "Duration: {duration} (from text file: '{raw}')"},
+    "tooltip_duration_invalid": {Language.DE: "Dauer: ungültig (Textdatei: '{raw}')",
+                                 Language.EN: "Duration: invalid (text file: '{raw}')"},
+    "tooltip_label": {Language.DE: "Labelcode: {code} (Label '{label}' ist Präfix von Index '{index}')",
+                      Language.EN: "Label code: {code} (label '{label}' is a prefix of index '{index}')"},
+    "tooltip_no_label": {Language.DE: "Labelcode: keiner (kein Label ist Präfix von Index '{index}')",
+                         Language.EN: "Label code: none (no label is a prefix of index '{index}')"},
+    "error_list_tip": {Language.DE: "Fehler und Warnungen des letzten Laufs. Klick zeigt die betroffene Datei, "
+                                    "Klick auf die Überschrift sortiert.",
+                       Language.EN: "Errors and warnings of the last run. Click to show the affected file, "
+                                    "click a header to sort."},
+    "export_errors_button": {Language.DE: "Fehler exportieren", Language.EN: "Export errors"},
+    "export_errors_button_tip": {Language.DE: "Speichert die Fehlerliste als Datei (z.B. für Support-Anfragen).",
+                                 Language.EN: "Saves the error list to a file (e.g. for support requests)."},
+    "errors_exported": {Language.DE: "Fehler exportiert nach: {path}", Language.EN: "Errors exported to: {path}"},
+    "error_type_kein_semikolon": {Language.DE: "Kein Semikolon", Language.EN: "No semicolon"},
+    "error_type_unvollstaendig": {Language.DE: "Unvollständige Zeile", Language.EN: "Incomplete line"},
+    "error_type_ungueltige_dauer": {Language.DE: "Ungültige Dauer", Language.EN: "Invalid duration"},
+    "error_type_negative_dauer": {Language.DE: "Negative Dauer", Language.EN: "Negative duration"},
+    "error_type_keine_tracks": {Language.DE: "Keine Tracks", Language.EN: "No tracks"},
+    "error_type_kein_labelcode": {Language.DE: "Kein Labelcode", Language.EN: "No label code"},
+    "error_type_abschnitt_dauer": {Language.DE: "Abschnitt weicht vom Ziel ab (Ist / Ziel)",
+                                   Language.EN: "Segment differs from target (actual / target)"},
+    "error_type_audioformat": {Language.DE: "Audioformat nicht unterstützt, Länge unbekannt",
+                               Language.EN: "Audio format not supported, length unknown"},
+    "error_type_cue_laenge": {Language.DE: "Cue-Zeitpunkt nach dem Ende der Audiodatei",
+                              Language.EN: "Cue time after the end of the audio file"},
+    "error_type_kein_audiofile": {Language.DE: "Kein passendes Audiofile für", Language.EN: "No matching audio file for"},
+    "severity_fehler": {Language.DE: "Fehler", Language.EN: "Error"},
+    "severity_warnung": {Language.DE: "Warnung", Language.EN: "Warning"},
+    "error_table_headers": {Language.DE: ["Schwere", "Datei", "Zeile", "Meldung"],
+                            Language.EN: ["Severity", "File", "Line", "Message"]},
+    "error_filter_alle": {Language.DE: "Alle Meldungen", Language.EN: "All messages"},
+    "error_filter_fehler": {Language.DE: "Nur Fehler", Language.EN: "Errors only"},
+    "error_filter_warnung": {Language.DE: "Nur Warnungen", Language.EN: "Warnings only"},
+    "error_filter_tip": {Language.DE: "Meldungen nach Schwere filtern.", Language.EN: "Filter messages by severity."},
+    "no_tracks_extracted": {Language.DE: "Keine Tracks extrahiert, alle Zeilen wurden ignoriert.",
+                            Language.EN: "No tracks extracted, all lines were ignored."},
+    "debug_no_rows": {Language.DE: "{path} enthält keine Zeilen.", Language.EN: "{path} contains no lines."},
+    "processing_nothing_extracted": {Language.DE: "Dateien geladen, aber nichts extrahiert (siehe Fehler).",
+                                     Language.EN: "Files loaded, but nothing extracted (see errors)."},
+    "processing_some_empty": {Language.DE: "\n{count} Datei(en) ohne Tracks (siehe Fehler).",
+                              Language.EN: "\n{count} file(s) without tracks (see errors)."},
+    "error_type_datei": {Language.DE: "Datei", Language.EN: "File"},
+    "labelcodes_reassigned": {Language.DE: "Labelcodes neu zugeordnet: {matched} von {total} Zeilen haben einen Labelcode.",
+                              Language.EN: "Label codes reassigned: {matched} of {total} rows have a label code."},
+    "add_labelcodes_button": {Language.DE: "Labelcodes-Datei hinzufügen", Language.EN: "Add label code file"},
+    "add_labelcodes_button_tip": {Language.DE: "Weitere Labelcodes-Datei laden. Bei gleichen Labels gilt die zuletzt "
+                                               "hinzugefügte Datei.",
+                                  Language.EN: "Load another label code file. For identical labels the file added "
+                                               "last wins."},
+    "labelcodes_file_added": {Language.DE: "{path} hinzugefügt, {count} Labelcodes geladen.",
+                              Language.EN: "{path} added, {count} label codes loaded."},
+    "labelcodes_file_known": {Language.DE: "{path} ist bereits geladen.", Language.EN: "{path} is already loaded."},
+    "labelcodes_conflicts_title": {Language.DE: "Abweichende Labelcodes", Language.EN: "Conflicting label codes"},
+    "labelcodes_conflicts_text": {Language.DE: "{count} Label(s) stehen mehrfach mit unterschiedlichen Codes in den "
+                                               "Labelcode-Dateien. Verwendet wird jeweils der spätere Eintrag bzw. der aus "
+                                               "der später geladenen Datei (siehe Details).",
+                                  Language.EN: "{count} label(s) appear several times with different codes in the "
+                                               "label code files. The later entry or the one from the file loaded later "
+                                               "is used (see details)."},
+    "labelcodes_conflict_line": {Language.DE: "{label}: {code} aus {file} statt {other_code} aus {other_file}",
+                                 Language.EN: "{label}: {code} from {file} instead of {other_code} from {other_file}"},
+    "tooltip_label_source": {Language.DE: "Labelcode aus {file}", Language.EN: "Label code from {file}"},
+    "aggregate_button": {Language.DE: "Aggregat exportieren", Language.EN: "Export aggregate"},
+    "aggregate_button_tip": {Language.DE: "Anzahl und Gesamtdauer je Labelcode über alle geladenen Dateien als CSV speichern.",
+                             Language.EN: "Save track count and total duration per label code across all loaded files as CSV."},
+    "aggregate_exported": {Language.DE: "Aggregat für {codes} Labelcode(s) gespeichert: {path}",
+                           Language.EN: "Aggregate for {codes} label code(s) saved: {path}"},
+    "aggregate_skipped": {Language.DE: "\n{count} Datei(en) übersprungen (siehe Fehlerprotokoll).",
+                          Language.EN: "\n{count} file(s) skipped (see error log)."},
+    "without_label_code": {Language.DE: "ohne Labelcode", Language.EN: "without label code"},
+    "compare_button": {Language.DE: "Mit letztem Export vergleichen", Language.EN: "Compare with last export"},
+    "compare_button_tip": {Language.DE: "Zeigt, welche Zeilen seit dem letzten Verarbeiten neu, geändert oder entfernt sind.",
+                           Language.EN: "Shows which rows were added, changed or removed since the last processing run."},
+    "compare_no_snapshot": {Language.DE: "Es wurde noch nichts exportiert.", Language.EN: "Nothing has been exported yet."},
+    "compare_no_changes": {Language.DE: "Keine Änderungen seit dem letzten Export.",
+                           Language.EN: "No changes since the last export."},
+    "diff_headers": {Language.DE: ["Status", "Datei", "Index", "Änderungen"],
+                     Language.EN: ["Status", "File", "Index", "Changes"]},
+    "diff_status_neu": {Language.DE: "Neu", Language.EN: "Added"},
+    "diff_status_geaendert": {Language.DE: "Geändert", Language.EN: "Changed"},
+    "diff_status_entfernt": {Language.DE: "Entfernt", Language.EN: "Removed"},
+    "diff_field": {Language.DE: "{field}: \"{old}\" → \"{new}\"", Language.EN: "{field}: \"{old}\" → \"{new}\""},
+    "close": {Language.DE: "Schließen", Language.EN: "Close"},
+    "status_counts": {Language.DE: "Dateien: {files} | Tracks: {tracks} | Fehler: {errors} | Warnungen: {warnings} | "
+                                   "Dauer: {duration}",
+                      Language.EN: "Files: {files} | Tracks: {tracks} | Errors: {errors} | Warnings: {warnings} | "
+                                   "Duration: {duration}"},
+    "status_files_added": {Language.DE: "{count} Datei(en) hinzugefügt", Language.EN: "{count} file(s) added"},
+    "status_files_removed": {Language.DE: "{count} Datei(en) entfernt", Language.EN: "{count} file(s) removed"},
+    "status_exported": {Language.DE: "CSV exportiert ({count} Datei(en))", Language.EN: "CSV exported ({count} file(s))"},
+    "status_aggregate_exported": {Language.DE: "Aggregat exportiert", Language.EN: "Aggregate exported"},
+    "status_errors_exported": {Language.DE: "Fehler exportiert", Language.EN: "Errors exported"},
+    "base_file_label": {Language.DE: "Basis:", Language.EN: "Base:"},
+    "base_file_tip": {Language.DE: "Diese Datei bestimmt den Namen der Ausgabe (output_{base}.csv)",
+                      Language.EN: "This file determines the output name (output_{base}.csv)"},
+    "toast_files_added": {Language.DE: "{count} Datei(en) geladen", Language.EN: "{count} file(s) loaded"},
+    "toast_processed": {Language.DE: "{tracks} Tracks exportiert, {errors} Fehler, {warnings} Warnungen",
+                        Language.EN: "{tracks} tracks exported, {errors} errors, {warnings} warnings"},
+    "labelcodes_reloaded": {Language.DE: "Labelcodes wurden neu geladen.", Language.EN: "Label codes reloaded."},
+    "output_dir": {Language.DE: "Ausgabeort: {path}", Language.EN: "Output folder: {path}"},
+    "append_off": {Language.DE: "Es werden wieder einzelne CSV-Dateien geschrieben.",
+                   Language.EN: "Separate CSV files will be written again."},
+    "append_dialog_title": {Language.DE: "CSV zum Anhängen wählen", Language.EN: "Choose CSV to append to"},
+    "append_target": {Language.DE: "Tracks werden angehängt an: {path}", Language.EN: "Tracks will be appended to: {path}"},
+    "select_dialog_title": {Language.DE: "Dateien auswählen", Language.EN: "Select files"},
+    "files_loaded": {Language.DE: "{count} Datei(en) geladen. ({added} neu)",
+                     Language.EN: "{count} file(s) loaded. ({added} new)"},
+    "files_dropped": {Language.DE: "{count} Datei(en) geladen. (+{added} neu)",
+                      Language.EN: "{count} file(s) loaded. (+{added} new)"},
+    "file_limit_title": {Language.DE: "Viele Dateien", Language.EN: "Many files"},
+    "file_limit_text": {Language.DE: "{count} Dateien hinzufügen? Damit wird die Grenze von {limit} Dateien "
+                                     "überschritten und die Oberfläche kann langsam werden.",
+                        Language.EN: "Add {count} files? This exceeds the limit of {limit} files "
+                                     "and the interface may become slow."},
+    "no_files": {Language.DE: "Keine Dateien geladen.", Language.EN: "No files loaded."},
+    "debug_error": {Language.DE: "Debug-Ansicht: Fehler beim Lesen von {path}: {error}",
+                    Language.EN: "Debug view: error reading {path}: {error}"},
+    "nothing_selected": {Language.DE: "Keine Datei zum Entfernen ausgewählt.", Language.EN: "No file selected for removal."},
+    "files_remaining": {Language.DE: "{count} Datei(en) verbleiben.", Language.EN: "{count} file(s) remaining."},
+    "no_files_to_process": {Language.DE: "Keine Dateien geladen. Bitte erst Dateien hinzufügen.",
+                            Language.EN: "No files loaded. Please add files first."},
+    "cancel_export_button": {Language.DE: "Export abbrechen", Language.EN: "Cancel export"},
+    "cancel_export_button_tip": {Language.DE: "Bricht den laufenden Export ab und entfernt die angefangene Datei.",
+                                 Language.EN: "Cancels the running export and removes the partially written file."},
+    "export_running": {Language.DE: "Es läuft bereits ein Export.", Language.EN: "An export is already running."},
+    "export_cancelling": {Language.DE: "Export wird abgebrochen ...", Language.EN: "Cancelling export ..."},
+    "export_checking": {Language.DE: "Dateien werden vor dem Export geprüft ...",
+                        Language.EN: "Checking files before the export ..."},
+    "export_cancelled": {Language.DE: "Export abgebrochen, {count} Datei(en) vollständig geschrieben.",
+                         Language.EN: "Export cancelled, {count} file(s) written completely."},
+    "processing_done": {Language.DE: "Verarbeitung abgeschlossen. Siehe ggf. error.log für Details.",
+                        Language.EN: "Processing finished. See error.log for details if needed."},
+    "processing_error": {Language.DE: "Fehler beim Verarbeiten: {error}", Language.EN: "Error while processing: {error}"},
+    "file_error": {Language.DE: "Fehler beim Verarbeiten von {path}: {error}",
+                   Language.EN: "Error while processing {path}: {error}"},
+    "format_vertauscht": {Language.DE: "{path}: Die Dauern stehen offenbar vor den Dateinamen. "
+                                        "Erwartet wird pro Zeile \"Dateiname;Dauer\" – Spalten tauschen?",
+                          Language.EN: "{path}: durations seem to come before the file names. "
+                                       "Expected \"file name;duration\" per line – swap the columns?"},
+    "format_abwechselnd": {Language.DE: "{path}: Dateinamen und Dauern stehen offenbar abwechselnd in eigenen Zeilen. "
+                                         "Erwartet wird pro Zeile \"Dateiname;Dauer\" – je zwei Zeilen zusammenfassen?",
+                           Language.EN: "{path}: file names and durations seem to alternate on separate lines. "
+                                        "Expected \"file name;duration\" per line – join each pair of lines?"},
+    "format_keine_dauern": {Language.DE: "{path}: Die zweite Spalte enthält überwiegend keine Dauern (z.B. 3:45). "
+                                          "Die Datei wurde nicht verarbeitet.",
+                            Language.EN: "{path}: the second column mostly contains no durations (e.g. 3:45). "
+                                         "The file was not processed."},
+    "file_not_found": {Language.DE: "Datei nicht gefunden: {path}", Language.EN: "File not found: {path}"},
+    "file_not_readable": {Language.DE: "Keine Leseberechtigung: {path}", Language.EN: "No read permission: {path}"},
+    "file_empty": {Language.DE: "Datei leer: {path}", Language.EN: "File is empty: {path}"},
+    "confirm_exit_title": {Language.DE: "Beenden", Language.EN: "Quit"},
+    "confirm_exit_text": {Language.DE: "{count} Datei(en) wurden noch nicht verarbeitet. Trotzdem beenden?",
+                          Language.EN: "{count} file(s) have not been processed yet. Quit anyway?"},
+    "confirm_exit_process": {Language.DE: "Verarbeiten und beenden", Language.EN: "Process and quit"},
+    "confirm_exit_discard": {Language.DE: "Ohne Verarbeiten beenden", Language.EN: "Quit without processing"},
+    "confirm_exit_export_text": {Language.DE: "Der Export läuft noch. Wird er abgebrochen, wird die angefangene "
+                                              "Ausgabedatei entfernt.",
+                                 Language.EN: "The export is still running. Cancelling it removes the output file "
+                                              "being written."},
+    "confirm_exit_after_export": {Language.DE: "Nach dem Export beenden", Language.EN: "Quit after the export"},
+    "confirm_exit_cancel_export": {Language.DE: "Export abbrechen und beenden", Language.EN: "Cancel export and quit"},
+    "summary": {Language.DE: ("Datei '{path}':\n"
+                              "  Gelesene Zeilen: {read}\n"
+                              "  Ignoriert (kein Semikolon): {no_semicolon}\n"
+                              "  Ignoriert (ungültige Dauer): {no_duration}\n"
+                              "  Ignoriert (allg. Fehler): {general}\n"),
+                Language.EN: ("File '{path}':\n"
+                              "  Lines read: {read}\n"
+                              "  Ignored (no semicolon): {no_semicolon}\n"
+                              "  Ignored (invalid duration): {no_duration}\n"
+                              "  Ignored (general error): {general}\n")},
+    "index_missing": {Language.DE: "  Index fehlt: {indexes}\n", Language.EN: "  Index missing: {indexes}\n"},
+    "index_duplicate": {Language.DE: "  Index doppelt: {indexes}\n", Language.EN: "  Index duplicated: {indexes}\n"},
+    "label_target_ok": {Language.DE: "  Labelcode {code}: {actual} (Ziel {target} erreicht)\n",
+                        Language.EN: "  Label code {code}: {actual} (target {target} met)\n"},
+    "label_target_failed": {Language.DE: "  Labelcode {code}: {actual} (Ziel {target} NICHT erreicht)\n",
+                            Language.EN: "  Label code {code}: {actual} (target {target} NOT met)\n"},
+    "segment_target_ok": {Language.DE: "  Abschnitt {start}-{end}: {actual} (Ziel {target})\n",
+                          Language.EN: "  Segment {start}-{end}: {actual} (target {target})\n"},
+    "segment_target_failed": {Language.DE: "  Abschnitt {start}-{end}: {actual} (Ziel {target}) WEICHT AB\n",
+                              Language.EN: "  Segment {start}-{end}: {actual} (target {target}) MISMATCH\n"},
+    "collapsed_duplicate": {Language.DE: "  Zusammengeführt: {filename} -> {other}\n",
+                            Language.EN: "  Merged: {filename} -> {other}\n"},
+    "summary_header": {Language.DE: "  Kopfzeile übersprungen: {header}\n",
+                       Language.EN: "  Header line skipped: {header}\n"},
+    "summary_placeholders": {Language.DE: "  Platzhalter-Dauer verwendet (mit * markiert): {count}\n",
+                             Language.EN: "  Placeholder duration used (marked with *): {count}\n"},
+    "summary_text_only_markieren": {Language.DE: "  Nur in der Textdatei (keine Audiodatei, markiert): {count}\n",
+                                    Language.EN: "  Only in the text file (no audio file, flagged): {count}\n"},
+    "summary_text_only_ausschliessen": {Language.DE: "  Nur in der Textdatei (keine Audiodatei, nicht exportiert): "
+                                                     "{count}\n",
+                                        Language.EN: "  Only in the text file (no audio file, not exported): {count}\n"},
+    "fixed_width_truncated": {Language.DE: "  Warnung: {count} Wert(e) für feste Breiten gekürzt (Spalten: {columns}).\n",
+                              Language.EN: "  Warning: {count} value(s) truncated for fixed widths (columns: {columns}).\n"},
+    "summary_output": {Language.DE: "  Ausgabe: {path}", Language.EN: "  Output: {path}"},
+    "warn_not_utf8": {Language.DE: "  Warnung: Bestehende CSV ist nicht UTF-8-kodiert.\n",
+                      Language.EN: "  Warning: existing CSV is not UTF-8 encoded.\n"},
+    "warn_columns_differ": {Language.DE: "  Warnung: Spalten der bestehenden CSV weichen ab ({columns}).\n",
+                            Language.EN: "  Warning: columns of the existing CSV differ ({columns}).\n"},
+}
+
+def system_language():
+    lang_code = locale.getlocale()[0] or ""
+    if lang_code.lower().startswith(("de", "german")):
+        return Language.DE
+    return Language.EN
+
+def language_from_config(value):
+    # Ohne Eintrag in der Config wird die Systemsprache verwendet
+    for language in Language:
+        if language.value == value:
+            return language
+    return system_language()
+
+def tr(key: str, language: Language, **kwargs):
+    text = TRANSLATIONS[key][language]
+    if kwargs:
+        return text.format(**kwargs)
+    return text
@@ -1,3 +1,54 @@
+import logging
+import os
+import sys
+from logging.handlers import RotatingFileHandler
+
+# Zusätzliches Protokoll im Datenordner des Benutzers, damit es auch ohne Terminal
+# nach einem Absturz verfügbar ist. error.log im Arbeitsordner bleibt wie bisher.
+LOG_LEVELS = ["aus", "ERROR", "INFO", "DEBUG"]
+LOG_FILE_NAME = 'gema.log'
+
+logger = logging.getLogger("gema")
+logger.propagate = False
+logger.setLevel(logging.CRITICAL + 1)  # Bis configure_file_logging aufgerufen wird: aus
+
+def log_dir():
+    if sys.platform.startswith('win'):
+        base = os.environ.get('LOCALAPPDATA') or os.path.expanduser('~')
+    elif sys.platform == 'darwin':
+        base = os.path.expanduser('~/Library/Application Support')
+    else:
+        base = os.environ.get('XDG_DATA_HOME') or os.path.expanduser('~/.local/share')
+    return os.path.join(base, 'GEMA')
+
+def configure_file_logging(level, directory=None):
+    # level: einer der LOG_LEVELS; "aus" schaltet das Protokoll ab. Liefert den Pfad der Datei oder None
+    for handler in list(logger.handlers):
+        logger.removeHandler(handler)
+        handler.close()
+    if level not in LOG_LEVELS[1:]:
+        logger.setLevel(logging.CRITICAL + 1)
+        return None
+    directory = directory or log_dir()
+    try:
+        os.makedirs(directory, exist_ok=True)
+        handler = RotatingFileHandler(os.path.join(directory, LOG_FILE_NAME), maxBytes=1024 * 1024,
+                                      backupCount=3, encoding='utf-8')
+    except OSError as e:
+        log_error(f"Protokolldatei konnte nicht angelegt werden: {e}")
+        return None
+    handler.setFormatter(logging.Formatter("%(asctime)s %(levelname)s %(message)s"))
+    logger.addHandler(handler)
+    logger.setLevel(getattr(logging, level))
+    return handler.baseFilename
+
+def log_info(message: str):
+    logger.info(message)
+
+def log_debug(message: str):
+    logger.debug(message)
+
 def log_error(message: str):
+    logger.error(message)
     with open('error.log', 'a', encoding='utf-8') as f:
         f.write(message + '\n')
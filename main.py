@@ -1,9 +1,130 @@
+import os
 import sys
-from PyQt5.QtWidgets import QApplication
-from gui import DragDropWindow
+import time
+
+from config import load_config, resolve_paths
+from logging_utils import configure_file_logging, log_info
+from processing import merge_labelcodes, expand_pattern, process_single_file, poll_hotfolder, ParseOptions
+
+def display_available():
+    # Ohne Display bricht Qt hart ab, daher vorher prüfen (nur unter Linux/Unix relevant)
+    if sys.platform.startswith('win') or sys.platform == 'darwin':
+        return True
+    return bool(os.environ.get('DISPLAY') or os.environ.get('WAYLAND_DISPLAY')
+                or os.environ.get('QT_QPA_PLATFORM'))
+
+# Pfad-Optionen der Kommandozeile, z.B. --labelcodes=/srv/gema/Labelcodes.txt
+PATH_OPTIONS = {"--labelcodes": "labelcodes_file", "--export-dir": "default_output_dir"}
+HOTFOLDER_INTERVAL = 2  # Sekunden zwischen zwei Blicken in den überwachten Ordner
+# Im strengen Modus (strict_mode) trotz offener Probleme exportieren
+ALLOW_PARTIAL_OPTION = "--allow-partial"
+
+def split_path_options(args):
+    cli_values, rest = {}, []
+    for arg in args:
+        option, _, value = arg.partition("=")
+        if option in PATH_OPTIONS and value:
+            cli_values[PATH_OPTIONS[option]] = value
+        else:
+            rest.append(arg)
+    return cli_values, rest
+
+def load_without_gui(cli_values):
+    config = load_config()
+    configure_file_logging(config.get("file_log_level", "aus"))
+    # Die Dateiprotokollierung ist meist aus; woher die Pfade kommen, soll man trotzdem sehen
+    paths = resolve_paths(config, cli_values, report=lambda message: print(message, file=sys.stderr))
+    label_dict, _, conflicts = merge_labelcodes([paths["labelcodes_file"]] + config.get("extra_labelcodes_files", []))
+    for label, code, source, other_code, other_source in conflicts:
+        print(f"Warnung: {label} hat {code} in {source}, aber {other_code} in {other_source}; verwendet wird {code}.",
+              file=sys.stderr)
+    return config, paths, label_dict
+
+def export_file(input_file, config, paths, label_dict, allow_partial=False):
+    summary, _ = process_single_file(input_file, paths["default_output_dir"], label_dict,
+                                     config.get("csv_columns", ["Index", "Titel", "Künstler", "Labelcode", "Dauer"]),
+                                     options=ParseOptions.from_config(config),
+                                     label_targets=config.get("label_code_targets", {}),
+                                     segment_targets=config.get("segment_targets", []),
+                                     csv_quoting=config.get("csv_quoting", "minimal"),
+                                     submission_id=config.get("submission_id", {}),
+                                     decimal_separator=config.get("decimal_separator", ","),
+                                     csv_headers=config.get("csv_headers", {}),
+                                     fixed_widths=config.get("fixed_widths", {}),
+                                     strict=config.get("strict_mode", False) and not allow_partial,
+                                     required_fields=config.get("required_fields", []))
+    return summary
+
+def run_without_gui(patterns, cli_values=None, allow_partial=False):
+    config, paths, label_dict = load_without_gui(cli_values)
+    files = [f for pattern in patterns for f in expand_pattern(pattern)]
+    if not files:
+        print("Keine .txt-Dateien gefunden.", file=sys.stderr)
+        return 1
+    for input_file in files:
+        print(export_file(input_file, config, paths, label_dict, allow_partial))
+    return 0
+
+def is_inside(path, directory):
+    try:
+        return os.path.commonpath([path, directory]) == directory
+    except ValueError:
+        # Verschiedene Laufwerke oder UNC-Pfad gegen lokalen Pfad (Windows): haben nichts miteinander zu tun
+        return False
+
+def run_hotfolder(directory, cli_values=None, allow_partial=False):
+    # Unbeaufsichtigter Betrieb: neue oder geänderte .txt-Dateien im Ordner sofort als CSV exportieren
+    config, paths, label_dict = load_without_gui(cli_values)
+    export_dir, directory = os.path.abspath(paths["default_output_dir"]), os.path.abspath(directory)
+    if is_inside(export_dir, directory):
+        # Sonst würden die eigenen Ausgaben (z.B. *_fest.txt) wieder eingelesen
+        print("Der Export-Ordner darf nicht im überwachten Ordner liegen.", file=sys.stderr)
+        return 1
+    state = {}
+    poll_hotfolder(directory, state)
+    state = {path: (signature, True) for path, (signature, _) in state.items()}  # Vorhandene Dateien nicht exportieren
+    print(f"Überwache {directory}, Export nach {paths['default_output_dir']} (Abbruch mit Strg+C).")
+    try:
+        while True:
+            time.sleep(HOTFOLDER_INTERVAL)
+            for input_file in poll_hotfolder(directory, state):
+                summary = export_file(input_file, config, paths, label_dict, allow_partial)
+                log_info(f"Hotfolder: {input_file} exportiert.")
+                print(summary)
+    except KeyboardInterrupt:
+        return 0
+
+def gui_unavailable(reason, patterns, cli_values=None, allow_partial=False):
+    # Ohne Fenster die Dateien der Kommandozeile direkt exportieren, sonst die Aufrufmöglichkeiten zeigen
+    print(f"GEMA: Das Fenster konnte nicht geöffnet werden ({reason}).", file=sys.stderr)
+    if patterns:
+        print("Verarbeite die angegebenen Dateien ohne Oberfläche.", file=sys.stderr)
+        sys.exit(run_without_gui(patterns, cli_values, allow_partial))
+    print("Dateien können ohne Oberfläche verarbeitet werden: python main.py [--labelcodes=PFAD] [--export-dir=ORDNER] "
+          "[--allow-partial] <datei|ordner|muster> ...\nOrdner überwachen und neue Dateien automatisch exportieren: "
+          "python main.py --watch=ORDNER [--export-dir=ORDNER]", file=sys.stderr)
+    sys.exit(1)
 
 if __name__ == '__main__':
+    cli_values, patterns = split_path_options(sys.argv[1:])
+    allow_partial = ALLOW_PARTIAL_OPTION in patterns
+    patterns = [arg for arg in patterns if arg != ALLOW_PARTIAL_OPTION]
+    watch = [arg.partition("=")[2] for arg in patterns if arg.startswith("--watch=")]
+    if watch:
+        # Hotfolder läuft immer ohne Oberfläche, das Fenster bleibt für den normalen Start
+        sys.exit(run_hotfolder(watch[0], cli_values, allow_partial))
+    if not display_available():
+        gui_unavailable("kein Display gefunden", patterns, cli_values, allow_partial)
+    try:
+        from PyQt5.QtWidgets import QApplication
+        from gui import DragDropWindow
+    except ImportError as e:
+        gui_unavailable(f"PyQt5 nicht verfügbar: {e}", patterns, cli_values, allow_partial)
+
     app = QApplication(sys.argv)
-    window = DragDropWindow()
+    window = DragDropWindow(cli_values)
+    if patterns:
+        # Dateien, Ordner oder Muster (z.B. "./daten/*.txt") direkt beim Start hinzufügen
+        window.add_patterns(patterns)
     window.show()
     sys.exit(app.exec_())
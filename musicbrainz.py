@@ -0,0 +1,45 @@
+import json
+import threading
+import time
+import urllib.parse
+import urllib.request
+
+API_URL = "https://musicbrainz.org/ws/2/recording/"
+USER_AGENT = "GEMA/1.0 (https://github.com/TJ-5/GEMA)"
+MIN_INTERVAL = 1.0  # MusicBrainz erlaubt höchstens eine Anfrage pro Sekunde
+
+_lock = threading.Lock()
+_last_request = 0.0
+
+def _wait_for_rate_limit():
+    global _last_request
+    with _lock:
+        delay = _last_request + MIN_INTERVAL - time.monotonic()
+        if delay > 0:
+            time.sleep(delay)
+        _last_request = time.monotonic()
+
+def build_query(title: str, artist: str):
+    parts = [f'recording:"{title}"'] if title else []
+    if artist:
+        parts.append(f'artist:"{artist}"')
+    return " AND ".join(parts)
+
+def lookup_recording(title: str, artist: str, timeout=10):
+    # Blockierender Aufruf, nur aus einem Hintergrund-Thread verwenden.
+    # Liefert (Titel, Künstler) des besten Treffers oder None
+    query = build_query(title, artist)
+    if not query:
+        return None
+    _wait_for_rate_limit()
+    url = API_URL + "?" + urllib.parse.urlencode({"query": query, "fmt": "json", "limit": 1})
+    request = urllib.request.Request(url, headers={"User-Agent": USER_AGENT})
+    with urllib.request.urlopen(request, timeout=timeout) as response:
+        data = json.load(response)
+    recordings = data.get("recordings") or []
+    if not recordings:
+        return None
+    recording = recordings[0]
+    artist_name = "".join(credit.get("name", "") + credit.get("joinphrase", "")
+                          for credit in recording.get("artist-credit", []))
+    return recording.get("title", ""), artist_name
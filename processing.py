@@ -1,32 +1,260 @@
 import os
 import csv
-from logging_utils import log_error
+import glob
+import json
+import ntpath
+import re
+import codecs
+import traceback
+import wave
+from dataclasses import dataclass, field, replace
+from typing import Optional
+from itertools import chain, islice
+from datetime import datetime
+from logging_utils import log_error, log_debug
+from i18n import Language, tr
+
+@dataclass(frozen=True)
+class TrackInfo:
+    # index ist normalisiert (klein) und dient dem Gruppieren und dem Labelcode-Abgleich,
+    # raw_index behält die Schreibweise aus dem Dateinamen für die Ausgabe
+    index: str
+    title: str
+    artist: str
+    label_code: str = ''
+    raw_index: str = field(default='', compare=False)
+    filename: str = field(default='', compare=False)
+    # Zählt gleiche Tracks durch, wenn ihre Dauern nicht zusammengefasst werden sollen
+    occurrence: int = 0
+    # Audioformat aus der Dateiendung (klein, z.B. "wav"), leer bei unbekannter Endung
+    ext: str = field(default='', compare=False)
+    # Aus Klammern im Dateinamen, z.B. "[POP]" und "(2019)"
+    genre: str = field(default='', compare=False)
+    year: str = field(default='', compare=False)
+    # Titel und Künstler in der Schreibweise aus dem Dateinamen (nach der Bereinigung), siehe track_text
+    raw_title: str = field(default='', compare=False)
+    raw_artist: str = field(default='', compare=False)
+    # Letzte Zahl des Index als Ganzzahl ("KPM1234_05" -> 5), None ohne Zahl; raw_index behält die Nullen
+    track_number: Optional[int] = field(default=None, compare=False)
+
+@dataclass
+class ParseOptions:
+    # 0 = Index endet nach dem ersten Token mit Ziffer (bisherige Heuristik)
+    index_token_count: int = 0
+    # 001 und 1 gelten als gleicher Index, wenn führende Nullen ignoriert werden
+    ignore_index_leading_zeros: bool = False
+    # Trennzeichen für einzeilige Einträge "index;titel;kuenstler;dauer", leer = automatisch erkennen
+    entry_delimiter: str = ''
+    # Dauern gleicher Tracks addieren statt jedes Vorkommen als eigene Zeile auszugeben; aus, da dieselbe
+    # Aufnahme auch zweimal gespielt worden sein kann
+    merge_durations: bool = False
+    # Platzhalter-Dauer (z.B. "3:00") für Zeilen ohne gültige Dauer, leer = solche Zeilen ignorieren
+    default_duration: str = ''
+    # Zeichen, die im Dateinamen Wörter trennen (mehrere möglich, z.B. "_-.")
+    separators: str = '_'
+    # Von Hand übernommene Korrekturen: Dateiname -> {"index": ..., "title": ..., "artist": ..., "label_code": ...,
+    # "duration": ...}
+    overrides: dict = field(default_factory=dict)
+    # Titel und Künstler bei Ausgabe und Anzeige in Title-Case ("John van Dyke") statt klein
+    normalize_case: bool = False
+    # Reguläre Ausdrücke mit den Gruppen index, title und artist, werden vor der Token-Heuristik probiert
+    filename_patterns: list = field(default_factory=list)
+    # Erste Zeile als Kopfzeile überspringen: "auto" (erkennen), "immer" oder "nie"
+    header_line: str = "auto"
+    # "[Genre]" und "(Jahr)" vor der Zerlegung aus dem Dateinamen lösen
+    bracket_metadata: bool = False
+    # Sendelisten: fehlende und doppelte Index-Nummern melden
+    check_index_sequence: bool = False
+    # Nach dem Zerlegen aus Index, Titel und Künstler entfernen: Zeichen am Anfang/Ende (z.B. "-._")
+    # und Ersetzungen [Muster, Ersatz] mit regulären Ausdrücken oder Regeln je Feld (siehe valid_replacements);
+    # Leerzeichen werden immer zusammengefasst
+    strip_chars: str = ''
+    field_replacements: list = field(default_factory=list)
+    # Dauer im Dateinamen, reguläre Ausdrücke mit den Gruppen min und sec, z.B. r"_(?P<min>\d+)m(?P<sec>\d+)s$"
+    # für "01_TITLE_Artist_3m45s.mp3"; der Treffer wird vor der Zerlegung aus dem Namen entfernt
+    duration_patterns: list = field(default_factory=list)
+    # Zahlen im Index bei der Ausgabe: "unveraendert", "ohne_nullen" (001 -> 1) oder "auffuellen" auf
+    # index_number_width Stellen (1 -> 001); Buchstaben bleiben, wie sie sind
+    index_numbers: str = "unveraendert"
+    index_number_width: int = 3
+    # Tracks, zu denen im Ordner der Textdatei keine Audiodatei liegt: "behalten" (nicht prüfen),
+    # "markieren" (als Warnung melden) oder "ausschliessen" (melden und nicht exportieren)
+    text_only_tracks: str = "behalten"
+    # Verbindet die Wörter des Künstlers, unabhängig von separators: " " (bisher), "_" behält z.B. "twenty_one_pilots"
+    artist_separator: str = ' '
+    # Spalten ("Titel", "Künstler"), die bei Ausgabe und Anzeige die Originalschreibweise statt der
+    # normalisierten (klein bzw. Title-Case) zeigen
+    original_case_columns: list = field(default_factory=list)
+
+    @classmethod
+    def from_config(cls, config):
+        return cls(index_token_count=int(config.get("index_token_count", 0)),
+                   ignore_index_leading_zeros=bool(config.get("ignore_index_leading_zeros", False)),
+                   entry_delimiter=config.get("entry_delimiter", ""),
+                   merge_durations=bool(config.get("merge_durations", False)),
+                   default_duration=config.get("default_duration", ""),
+                   separators=config.get("separators", "_") or "_",
+                   normalize_case=bool(config.get("normalize_case", False)),
+                   filename_patterns=list(config.get("filename_patterns", [])),
+                   header_line=config.get("header_line", "auto"),
+                   bracket_metadata=bool(config.get("bracket_metadata", False)),
+                   check_index_sequence=bool(config.get("check_index_sequence", False)),
+                   strip_chars=config.get("strip_chars", ""),
+                   field_replacements=valid_replacements(config.get("field_replacements", [])),
+                   duration_patterns=list(config.get("filename_duration_patterns", [])),
+                   index_numbers=config.get("index_numbers", "unveraendert"),
+                   index_number_width=int(config.get("index_number_width", 3)),
+                   text_only_tracks=config.get("text_only_tracks")
+                   or ("markieren" if config.get("check_audio_files") else "behalten"),
+                   artist_separator=config.get("artist_separator", " "),
+                   original_case_columns=list(config.get("original_case_columns", [])))
+
+# Reihenfolge der Felder, wie extract_fields sie liefert
+FIELD_NAMES = ("index", "title", "artist")
+
+# Feld einer Regel in der Config -> Name des Felds beim Zerlegen; "alle" gilt für Index, Titel und Künstler
+RULE_FIELDS = {"alle": None, "index": "index", "titel": "title", "kuenstler": "artist"}
+
+def rule_replacement(rule):
+    # {"feld": "kuenstler", "suchen": "AC DC", "ersetzen": "AC/DC", "regex": false} -> (Muster, Ersatz, Feld);
+    # ohne regex wird der Text wörtlich und ohne Rücksicht auf Groß-/Kleinschreibung gesucht
+    field_name = RULE_FIELDS[rule.get("feld", "alle")]
+    search, replacement = rule["suchen"], rule.get("ersetzen", "")
+    if not search:
+        raise ValueError("leerer Suchtext")
+    if not rule.get("regex", False):
+        search, replacement = "(?i)" + re.escape(search), replacement.replace("\\", "\\\\")
+    return search, replacement, field_name
+
+def valid_replacements(replacements):
+    # Ungültige Muster protokollieren und auslassen, statt später jede Zeile scheitern zu lassen.
+    # Einträge sind [Muster, Ersatz] für alle Felder oder Regeln wie in rule_replacement, in dieser Reihenfolge
+    valid = []
+    for entry in replacements:
+        try:
+            if isinstance(entry, dict):
+                pattern, replacement, field_name = rule_replacement(entry)
+            else:
+                pattern, replacement = entry
+            re.compile(pattern)
+        except (KeyError, TypeError, ValueError, re.error) as e:
+            log_error(f"Ungültige Ersetzung in field_replacements: {entry!r} ({e})")
+            continue
+        valid.append((pattern, replacement, field_name) if isinstance(entry, dict) else (pattern, replacement))
+    return valid
+
+def clean_field(text: str, options, name=None):
+    # name: "index", "title" oder "artist"; Regeln für ein anderes Feld werden übersprungen
+    for pattern, replacement, *rule_field in options.field_replacements:
+        if rule_field and rule_field[0] not in (None, name):
+            continue
+        text = re.sub(pattern, replacement, text)
+    if options.strip_chars:
+        text = text.strip(options.strip_chars + ' ')
+    return ' '.join(text.split())
+
+# Werden außer am Anfang kleingeschrieben
+CASE_PARTICLES = {"van", "von", "der", "den", "de", "da", "del", "di", "du", "la", "le", "y",
+                  "the", "and", "of", "a", "an", "feat", "vs", "und"}
+
+def normalize_case(text: str):
+    words = []
+    for i, word in enumerate(text.split(' ')):
+        if i > 0 and word.lower() in CASE_PARTICLES:
+            words.append(word.lower())
+        else:
+            words.append('-'.join(part[:1].upper() + part[1:].lower() for part in word.split('-')))
+    return ' '.join(words)
+
+def display_text(text: str, options=None):
+    return normalize_case(text) if options and options.normalize_case else text
+
+# Spalten, die wahlweise die Originalschreibweise zeigen -> Feld von TrackInfo
+ORIGINAL_CASE_FIELDS = {"titel": "title", "künstler": "artist"}
+
+def track_text(track, column: str, options=None):
+    # Titel oder Künstler für die Spalte column: Originalschreibweise, wenn die Spalte in
+    # options.original_case_columns steht (und bekannt ist), sonst normalisiert
+    name = ORIGINAL_CASE_FIELDS[column.lower()]
+    raw = getattr(track, "raw_" + name)
+    if raw and options and column.lower() in (c.lower() for c in options.original_case_columns):
+        return raw
+    return display_text(getattr(track, name), options)
+
+# Bekannte Audio-Endungen; Muster und Ausgabe werden daraus erzeugt
+AUDIO_EXTENSIONS = ["wav", "mp3", "aif", "aiff", "flac", "m4a", "ogg"]
+AUDIO_EXTENSION_PATTERN = re.compile(r'\.(?P<ext>' + '|'.join(AUDIO_EXTENSIONS) + r')$', re.IGNORECASE)
+
+def audio_extension(filename: str):
+    match = AUDIO_EXTENSION_PATTERN.search(filename)
+    return match.group("ext").lower() if match else ''
 
 def remove_extension(filename: str):
     parts = filename.split('.', 1)
     return parts[0]
 
-def parse_track_filename(filename: str):
-    original_base = remove_extension(filename)
-    base = original_base.replace('_', ' ')
+GENRE_PATTERN = re.compile(r'\[([^\]]*)\]')
+YEAR_PATTERN = re.compile(r'\((\d{4})\)')
+
+def extract_bracket_metadata(text: str):
+    # Liefert (Text ohne die Klammern, Genre, Jahr); mehrere Genres werden mit ", " verbunden
+    genres = [g.strip() for g in GENRE_PATTERN.findall(text) if g.strip()]
+    years = YEAR_PATTERN.findall(text)
+    text = YEAR_PATTERN.sub(' ', GENRE_PATTERN.sub(' ', text))
+    return text, ", ".join(genres), years[0] if years else ''
+
+def is_upper_token(t):
+    letters = [c for c in t if c.isalpha()]
+    if not letters:
+        return False
+    return t.isupper()
+
+def looks_swapped(title: str, artist: str):
+    # Nach der Großbuchstaben-Regel sieht der Künstler wie ein Titel aus, der Titel aber nicht
+    return (any(is_upper_token(t) for t in artist.split())
+            and not any(is_upper_token(t) for t in title.split()))
+
+def find_filename_duration(base: str, options):
+    # base: Dateiname ohne Endung. Liefert (Name ohne Dauer, Dauer wie parse_duration) oder (base, None)
+    for pattern in options.duration_patterns:
+        match = re.search(pattern, base)
+        if match:
+            duration = parse_duration(f"{match.group('min')}:{match.group('sec')}")
+            return base[:match.start()] + base[match.end():], duration
+    return base, None
+
+def filename_duration(filename: str, options):
+    return find_filename_duration(remove_extension(filename), options)[1]
+
+def split_track_filename(filename: str, options=None):
+    options = options or ParseOptions()
+    if '.' in options.separators:
+        # Punkte trennen Wörter, daher nur die letzte Endung entfernen
+        original_base = filename.rsplit('.', 1)[0]
+    else:
+        original_base = remove_extension(filename)
+    original_base = find_filename_duration(original_base, options)[0]
+    if options.bracket_metadata:
+        original_base = extract_bracket_metadata(original_base)[0]
+    base = re.sub('[' + re.escape(options.separators) + ']', ' ', original_base)
     tokens = base.split()
 
     def contains_digit(t):
         return any(ch.isdigit() for ch in t)
-    
-    def is_upper_token(t):
-        letters = [c for c in t if c.isalpha()]
-        if not letters:
-            return False
-        return t.isupper()
 
     state = 'BEFORE_DIGIT'
     index_tokens = []
     title_tokens = []
     artist_tokens = []
     
-    for t in tokens:
-        if state == 'BEFORE_DIGIT':
+    for i, t in enumerate(tokens):
+        if i < options.index_token_count:
+            # Fest eingestellte Anzahl Index-Tokens, danach wird nach dem Titel gesucht
+            index_tokens.append(t)
+            if i == options.index_token_count - 1:
+                state = 'AFTER_DIGIT_BEFORE_TITLE'
+        
+        elif state == 'BEFORE_DIGIT':
             index_tokens.append(t)
             if contains_digit(t):
                 state = 'AFTER_DIGIT_BEFORE_TITLE'
@@ -39,7 +267,8 @@ def parse_track_filename(filename: str):
                 index_tokens.append(t)
         
         elif state == 'TITLE':
-            if is_upper_token(t):
+            # Zahlen ohne Buchstaben gehören zum Titel ("TRACK 2 THE FUTURE"), erst Kleinschreibung beginnt den Künstler
+            if is_upper_token(t) or not any(c.isalpha() for c in t):
                 title_tokens.append(t)
             else:
                 artist_tokens.append(t)
@@ -48,18 +277,96 @@ def parse_track_filename(filename: str):
         else:  # ARTIST
             artist_tokens.append(t)
     
+    return tokens, index_tokens, title_tokens, artist_tokens
+
+def parse_track_filename(filename: str, options=None):
+    options = options or ParseOptions()
+    _, index_tokens, title_tokens, artist_tokens = split_track_filename(filename, options)
     index_str = '_'.join(index_tokens).strip().lower()
     title_str = ' '.join(title_tokens).strip().lower()
-    artist_str = ' '.join(artist_tokens).strip().lower()
+    artist_str = options.artist_separator.join(artist_tokens).strip().lower()
     
     return index_str, title_str, artist_str
 
+def strip_index_leading_zeros(index_str: str):
+    return re.sub(r'\d+', lambda m: str(int(m.group())), index_str)
+
+def format_index_numbers(index_str: str, options):
+    if options.index_numbers == "ohne_nullen":
+        return strip_index_leading_zeros(index_str)
+    if options.index_numbers == "auffuellen":
+        return re.sub(r'\d+', lambda m: str(int(m.group())).zfill(options.index_number_width), index_str)
+    return index_str
+
+# Extraktoren bekommen (Dateiname, ParseOptions) und liefern (Index, Titel, Künstler)
+# in Originalschreibweise oder None, wenn der Dateiname nicht zu ihnen passt
+def token_extractor(filename: str, options):
+    _, index_tokens, title_tokens, artist_tokens = split_track_filename(filename, options)
+    return ('_'.join(index_tokens).strip(), ' '.join(title_tokens).strip(),
+            options.artist_separator.join(artist_tokens).strip())
+
+def regex_extractor(pattern: str):
+    regex = re.compile(pattern)
+
+    def extract(filename, options):
+        match = regex.fullmatch(find_filename_duration(remove_extension(filename), options)[0])
+        if not match:
+            return None
+        groups = match.groupdict()
+
+        def words(name, joiner=' '):
+            return joiner.join(re.sub('[' + re.escape(options.separators) + ']', ' ', groups.get(name) or '').split())
+        return (groups.get("index") or '').strip(), words("title"), words("artist", options.artist_separator)
+    return extract
+
+# Die Token-Heuristik passt immer und bleibt daher am Ende der Liste
+FIELD_EXTRACTORS = [token_extractor]
+
+def register_extractor(extractor):
+    # Eigene Extraktoren werden vor der Token-Heuristik probiert, in der Reihenfolge der Registrierung
+    FIELD_EXTRACTORS.insert(len(FIELD_EXTRACTORS) - 1, extractor)
+
+def extract_fields(filename: str, options):
+    extractors = [regex_extractor(p) for p in options.filename_patterns] + FIELD_EXTRACTORS
+    for extractor in extractors:
+        fields = extractor(filename, options)
+        if fields:
+            return fields
+    return '', '', ''
+
+def parse_track_info(filename: str, label_dict: dict, options=None):
+    options = options or ParseOptions()
+    raw_index, raw_title, raw_artist = (clean_field(f, options, name)
+                                        for f, name in zip(extract_fields(filename, options), FIELD_NAMES))
+    override = options.overrides.get(filename, {})
+    raw_index = override.get("index", raw_index)
+    idx, title, artist = raw_index.lower(), raw_title.lower(), raw_artist.lower()
+    if options.ignore_index_leading_zeros:
+        idx = strip_index_leading_zeros(idx)
+    # Von Hand korrigierte Werte gelten auch als Originalschreibweise
+    title, raw_title = override.get("title", title), override.get("title", raw_title)
+    artist, raw_artist = override.get("artist", artist), override.get("artist", raw_artist)
+    label_code = override.get("label_code") or find_label_code(idx, label_dict)
+    _, genre, year = extract_bracket_metadata(filename) if options.bracket_metadata else ('', '', '')
+    return TrackInfo(idx, title, artist, label_code, raw_index, filename, ext=audio_extension(filename),
+                     genre=genre, year=year, raw_title=raw_title, raw_artist=raw_artist,
+                     track_number=index_number(raw_index))
+
 def parse_duration(duration_str: str):
-    duration_str = duration_str.replace(':', '.')
+    # Deutsches Dezimalkomma ("3,45") wie Punkt und Doppelpunkt behandeln
+    duration_str = duration_str.replace(':', '.').replace(',', '.')
+    if duration_str.strip().startswith('-'):
+        return None  # Negative Dauern verfälschen die Summen, siehe duration_error_type
     parts = duration_str.split('.')
     
     if len(parts) < 2:
         return None
+    if len(parts) == 3 and all(p.strip().isdigit() for p in parts):
+        # Uhrzeit-Form H:MM:SS, auch mit führenden Nullstunden ("00:03:45"); Stunden zählen als Minuten
+        hours, minutes, seconds = (int(p) for p in parts)
+        if minutes > 59 or seconds > 59:
+            return None
+        return float(f"{hours * 60 + minutes}.{seconds:02d}")
     
     main_part = parts[0]
     decimal_part = parts[1]
@@ -71,123 +378,1185 @@ def parse_duration(duration_str: str):
     except ValueError:
         return None
 
+def duration_seconds(value: float):
+    # Wert wie parse_duration (Minuten.Sekunden) in echte Sekunden, auch für Summen wie 2.75 ("2:75" = 3:15)
+    minutes = int(value)
+    return minutes * 60 + round((value - minutes) * 100)
+
+def seconds_to_duration(seconds: float):
+    # Echte Sekunden in Minuten.Sekunden wie parse_duration, auf ganze Sekunden gerundet
+    total = int(round(seconds))
+    return float(f"{total // 60}.{total % 60:02d}")
+
+def add_durations(first: float, second: float):
+    # Minuten.Sekunden lassen sich nicht einfach addieren (0:59 + 0:59 wäre "1:118"), daher über echte Sekunden
+    return seconds_to_duration(duration_seconds(first) + duration_seconds(second))
+
+def duration_error_type(duration_str: str):
+    # Fehlertyp für eine Dauer, die parse_duration ablehnt
+    return "negative_dauer" if duration_str.strip().startswith('-') else "ungueltige_dauer"
+
 def format_duration(seconds: float):
     total_hundredths = int(round(seconds * 100))
     s = total_hundredths // 100
     ms = total_hundredths % 100
     return f"{s}:{ms:02d}"
 
-def load_labelcodes(labelcodes_file: str):
-    label_dict = {}
+def format_seconds(seconds: float):
+    # Echte Sekunden (z.B. Summen aus duration_seconds) als "M:SS", 195 -> "3:15"
+    total = int(round(seconds))
+    return f"{total // 60}:{total % 60:02d}"
+
+class JsonObject(dict):
+    # JSON-Objekt, das zusätzlich alle Schlüssel-Wert-Paare in Dateireihenfolge behält, auch doppelte
+    def __init__(self, pairs):
+        super().__init__(pairs)
+        self.pairs = pairs
+
+def labelcode_entries_from_json(data):
+    # {"Label": "Code", ...} oder [{"label": "Label", "code": "Code"}, ...]; None bei anderer Form.
+    # Doppelte Schlüssel bleiben als eigene Einträge erhalten, der letzte gewinnt (siehe merge_labelcodes)
+    if isinstance(data, dict):
+        pairs = getattr(data, "pairs", list(data.items()))
+        if all(isinstance(v, (str, int)) for _, v in pairs):
+            return [(str(label), str(code)) for label, code in pairs]
+    if isinstance(data, list) and all(isinstance(e, dict) and "label" in e and "code" in e for e in data):
+        return [(str(e["label"]), str(e["code"])) for e in data]
+    return None
+
+def read_labelcode_entries(labelcodes_file: str):
+    # Abwechselnd Label- und Code-Zeilen, Schreibweise der Labels bleibt erhalten.
+    # Fremde Dateien dürfen auch JSON sein, siehe labelcode_entries_from_json
+    entries = []
     if not os.path.exists(labelcodes_file):
-        return label_dict
-    with open(labelcodes_file, 'r', encoding='utf-8') as f:
-        lines = [l.strip() for l in f if l.strip()]
+        return entries
+    with open(labelcodes_file, 'r', encoding='utf-8-sig') as f:
+        content = f.read()
+    if content.lstrip().startswith(('{', '[')):
+        try:
+            entries = labelcode_entries_from_json(json.loads(content, object_pairs_hook=JsonObject))
+        except ValueError as e:
+            log_error(f"Labelcodes-Datei {labelcodes_file}: ungültiges JSON ({e})")
+            return []
+        if entries is None:
+            log_error(f"Labelcodes-Datei {labelcodes_file}: unbekannte JSON-Form. Erwartet wird "
+                      '{"Label": "Code", ...} oder [{"label": "Label", "code": "Code"}, ...]')
+            return []
+        return entries
+    lines = [l.strip() for l in content.splitlines() if l.strip()]
     for i in range(0, len(lines), 2):
         label = lines[i].strip()
         code = lines[i+1].strip() if i+1 < len(lines) else ''
+        entries.append((label, code))
+    return entries
+
+def load_labelcodes(labelcodes_file: str):
+    label_dict = {}
+    for label, code in read_labelcode_entries(labelcodes_file):
         label_dict[label.lower()] = code
     return label_dict
 
-def find_label_code(index_str: str, label_dict: dict):
+def merge_labelcodes(labelcodes_files):
+    # Der spätere Eintrag gewinnt, innerhalb einer Datei wie über Dateien hinweg: zuerst die Hauptdatei, dann die
+    # zusätzlichen Dateien in der Reihenfolge des Hinzufügens. Liefert (label_dict, Label -> Datei, Konflikte);
+    # ein Konflikt ist (Label, verwendeter Code, dessen Datei, überschriebener Code, dessen Datei)
+    label_dict, sources, conflicts = {}, {}, []
+    for labelcodes_file in labelcodes_files:
+        for label, code in read_labelcode_entries(labelcodes_file):
+            key = label.lower()
+            if key in label_dict and label_dict[key] != code:
+                conflicts.append((key, code, labelcodes_file, label_dict[key], sources[key]))
+            label_dict[key] = code
+            sources[key] = labelcodes_file
+    return label_dict, sources, conflicts
+
+def validate_labelcode_entries(entries, language=Language.DE):
+    problems = []
+    seen = set()
+    for row, (label, code) in enumerate(entries, start=1):
+        if not label.strip() or not code.strip():
+            problems.append(tr("labelcode_empty", language, row=row))
+        key = label.strip().lower()
+        if key and key in seen:
+            problems.append(tr("labelcode_duplicate", language, row=row, label=label.strip()))
+        seen.add(key)
+    return problems
+
+def save_labelcodes(labelcodes_file: str, entries):
+    if labelcodes_file.lower().endswith('.json'):
+        # JSON-Dateien bleiben JSON, damit andere Programme sie weiter lesen können
+        with open(labelcodes_file, 'w', encoding='utf-8') as f:
+            json.dump({label.strip(): code.strip() for label, code in entries}, f, indent=2, ensure_ascii=False)
+        return
+    with open(labelcodes_file, 'w', encoding='utf-8') as f:
+        for label, code in entries:
+            f.write(f"{label.strip()}\n{code.strip()}\n")
+
+@dataclass(frozen=True)
+class LabelMatch:
+    code: str
+    matched_prefix: str  # Label aus der Labelcodes-Datei (klein), das als Präfix gepasst hat
+
+def find_label_match(index_str: str, label_dict: dict):
+    # Labels sind klein gespeichert, daher wird unabhängig von der Schreibweise verglichen.
+    # Das längste passende Label gewinnt (z.B. "kpm12" vor "kpm"), bei gleicher Länge das erste.
+    # Liefert LabelMatch oder None
+    index_str = index_str.lower()
+    best = None
     for label, code in label_dict.items():
-        if index_str.startswith(label):
-            return code
-    return ''
+        if index_str.startswith(label) and (best is None or len(label) > len(best.matched_prefix)):
+            best = LabelMatch(code, label)
+    return best
 
-def list_txt_files_in_dir(directory):
+def find_label_code(index_str: str, label_dict: dict):
+    match = find_label_match(index_str, label_dict)
+    return match.code if match else ''
+
+def debug_parse_file(input_file, label_dict, options=None):
+    # Zeigt für jede Zeile, wie der Dateiname in Tokens zerlegt und zugeordnet wurde
+    options = options or ParseOptions()
+    rows = []
+    with open(input_file, 'r', encoding='utf-8-sig') as infile:
+        for line in infile:
+            line = line.strip()
+            if not line:
+                continue
+            parts = line.split(';', 1)
+            filename = parts[0].strip()
+            duration_str = parts[1].strip() if len(parts) > 1 else ''
+            duration_str = options.overrides.get(filename, {}).get("duration") or duration_str
+            tokens, index_tokens, title_tokens, artist_tokens = split_track_filename(filename, options)
+            track = parse_track_info(filename, label_dict, options)
+            raw_fields = extract_fields(filename, options)
+            _, raw_title, raw_artist = raw_fields
+            duration_in_seconds = parse_duration(duration_str)
+            if duration_in_seconds is None:
+                duration_in_seconds = filename_duration(filename, options)
+            duration = format_duration(duration_in_seconds) if duration_in_seconds is not None else None
+            if duration is None and parse_duration(options.default_duration) is not None:
+                duration = format_duration(parse_duration(options.default_duration)) + "*"
+            match = find_label_match(track.index, label_dict)
+            rows.append({
+                "filename": filename,
+                "tokens": tokens,
+                "index_tokens": index_tokens,
+                "title_tokens": title_tokens,
+                "artist_tokens": artist_tokens,
+                "index": track.index,
+                "raw_index": track.raw_index,
+                "title": track_text(track, "Titel", options),
+                "artist": track_text(track, "Künstler", options),
+                "label_code": track.label_code,
+                "matched_label": match.matched_prefix if match else None,
+                "duration_raw": duration_str,
+                "duration": duration,
+                # Vorher/Nachher der Bereinigung für die Felder, die sich dadurch geändert haben
+                "cleanup": [(name, before, clean_field(before, options, name))
+                            for name, before in zip(FIELD_NAMES, raw_fields)
+                            if clean_field(before, options, name) != before],
+                "swap_hint": filename not in options.overrides and looks_swapped(raw_title, raw_artist),
+            })
+    return rows
+
+# Felder der Debug-Ansicht für Suchen und Ersetzen, zugleich die Schlüssel der Korrekturen (overrides)
+BULK_REPLACE_FIELDS = ["index", "title", "artist", "label_code"]
+
+def bulk_replace(rows, field_name, search, replacement, case_sensitive=False, whole_word=False):
+    # Einmalige Korrektur über alle Zeilen von debug_parse_file, wörtlich gesucht. Liefert
+    # (Dateiname, vorher, nachher) der Zeilen, die sich ändern; jeder Dateiname nur einmal
+    if not search:
+        return []
+    pattern = re.escape(search)
+    if whole_word:
+        pattern = r'(?<!\w)' + pattern + r'(?!\w)'
+    regex = re.compile(pattern, 0 if case_sensitive else re.IGNORECASE)
+    key = "raw_index" if field_name == "index" else field_name
+    changes, seen = [], set()
+    for row in rows:
+        before = row[key] or ''
+        after = regex.sub(lambda match: replacement, before)
+        if after != before and row["filename"] not in seen:
+            seen.add(row["filename"])
+            changes.append((row["filename"], before, after))
+    return changes
+
+# Arten von Änderungen, die der Export an den Werten aus der Textdatei vornimmt
+TRANSFORM_KINDS = ["klein", "bereinigt", "gerundet"]
+
+def export_transforms(input_file, label_dict, options=None):
+    # Liefert (Art, Dateiname, vorher, nachher) für jede Änderung: Kleinschreibung von Titel und Künstler,
+    # Bereinigung (strip_chars, Ersetzungen, Leerzeichen) und auf Hundertstel gerundete Dauern
+    options = options or ParseOptions()
+    transforms = []
+    for row in debug_parse_file(input_file, label_dict, options):
+        filename = row["filename"]
+        transforms += [("bereinigt", filename, before, after) for _, before, after in row["cleanup"]]
+        if filename not in options.overrides:
+            # Von Hand eingetragene Werte behalten ihre Schreibweise
+            _, raw_title, raw_artist = extract_fields(filename, options)
+            for name, before, after in [("title", raw_title, row["title"]), ("artist", raw_artist, row["artist"])]:
+                before = clean_field(before, options, name)
+                if before != after:
+                    transforms.append(("klein", filename, before, after))
+        seconds = parse_duration(row["duration_raw"])
+        if seconds is not None and round(seconds, 2) != seconds:
+            transforms.append(("gerundet", filename, row["duration_raw"], format_duration(seconds)))
+    return transforms
+
+def label_code_totals(track_dict):
+    # Labelcode -> Gesamtdauer in echten Sekunden; Minuten.Sekunden-Werte lassen sich nicht direkt addieren
+    totals = {}
+    for track, seconds in track_dict.items():
+        totals[track.label_code] = totals.get(track.label_code, 0) + duration_seconds(seconds)
+    return totals
+
+# Felder, die beim Vergleich mit dem letzten Export geprüft werden
+SNAPSHOT_FIELDS = ["title", "artist", "label_code", "duration"]
+
+def snapshot_rows(track_dict, options=None):
+    # Stand eines Exports: Index -> Felder wie in der CSV; wiederholte Indizes bekommen "#2", "#3", ...
+    rows = {}
+    for track, seconds in track_dict.items():
+        key = base = track.raw_index or track.index
+        number = 1
+        while key in rows:
+            number += 1
+            key = f"{base}#{number}"
+        rows[key] = {"title": track_text(track, "Titel", options), "artist": track_text(track, "Künstler", options),
+                     "label_code": track.label_code, "duration": format_duration(seconds)}
+    return rows
+
+def compare_snapshots(old_rows, new_rows):
+    # Liefert (Status, Index, [(Feld, alt, neu), ...]) mit Status "neu", "geaendert" oder "entfernt"
+    changes = []
+    for key, row in new_rows.items():
+        if key not in old_rows:
+            changes.append(("neu", key, []))
+            continue
+        fields = [(f, old_rows[key].get(f, ''), row[f]) for f in SNAPSHOT_FIELDS if old_rows[key].get(f, '') != row[f]]
+        if fields:
+            changes.append(("geaendert", key, fields))
+    changes.extend(("entfernt", key, []) for key in old_rows if key not in new_rows)
+    return changes
+
+def label_code_aggregate(track_dicts):
+    # Über mehrere Dateien: Labelcode -> (Anzahl Tracks, Gesamtdauer in echten Sekunden); ohne Labelcode unter ""
+    aggregate = {}
+    for track_dict in track_dicts:
+        for track, seconds in track_dict.items():
+            count, total = aggregate.get(track.label_code, (0, 0))
+            aggregate[track.label_code] = (count + 1, total + duration_seconds(seconds))
+    return aggregate
+
+def write_label_aggregate(csv_file, aggregate, language=Language.DE):
+    # "ohne Labelcode" steht immer am Ende, damit in der Summe nichts fehlt
+    with open(csv_file, 'w', newline='', encoding='utf-8') as f:
+        writer = csv.writer(f, delimiter=';')
+        writer.writerow(["Labelcode", "Anzahl", "Gesamtdauer"])
+        for code in sorted(c for c in aggregate if c):
+            count, seconds = aggregate[code]
+            writer.writerow([code, count, format_seconds(seconds)])
+        count, seconds = aggregate.get('', (0, 0))
+        writer.writerow([tr("without_label_code", language), count, format_seconds(seconds)])
+
+def check_label_code_targets(totals, label_targets):
+    # Ziele stehen wie die Dauern in den Textdateien, z.B. "12:30"; erfüllt ist ein Ziel, wenn es erreicht wird.
+    # totals wie label_code_totals; Liefert (Labelcode, Ist, Ziel, erfüllt) in echten Sekunden
+    results = []
+    for code, target_str in label_targets.items():
+        target = parse_duration(target_str)
+        if target is None:
+            log_error(f"Ungültiges Ziel für Labelcode {code}: '{target_str}'")
+            continue
+        actual, target = totals.get(code, 0), duration_seconds(target)
+        results.append((code, actual, target, actual >= target))
+    return results
+
+def index_number(index: str):
+    # Letzte Zahl im Index ("kpm1234_05" -> 5), wie bei check_index_sequence; None ohne Zahl am Ende
+    match = re.fullmatch(r'.*?(\d+)', index)
+    return int(match.group(1)) if match else None
+
+def check_segment_targets(track_dict, segments):
+    # Abschnitte einer Sendung nach Index-Nummer, z.B. {"von": 1, "bis": 10, "ziel": "20:00", "toleranz": "0:10"}.
+    # Liefert (von, bis, Ist, Ziel, erfüllt) mit Ist und Ziel in echten Sekunden; erfüllt, wenn die Summe
+    # höchstens um die Toleranz abweicht
+    results = []
+    for segment in segments:
+        try:
+            start, end = int(segment["von"]), int(segment["bis"])
+            target = parse_duration(str(segment["ziel"]))
+            tolerance = parse_duration(str(segment.get("toleranz", "0:00")))
+        except (KeyError, TypeError, ValueError):
+            target = None
+        if target is None or tolerance is None:
+            log_error(f"Ungültiger Abschnitt in segment_targets: {segment!r}")
+            continue
+        actual = sum(duration_seconds(seconds) for track, seconds in track_dict.items()
+                     if index_number(track.index) is not None and start <= index_number(track.index) <= end)
+        ok = abs(actual - duration_seconds(target)) <= duration_seconds(tolerance)
+        results.append((start, end, actual, duration_seconds(target), ok))
+    return results
+
+def check_index_sequence(tracks):
+    # Gruppiert nach dem Teil vor der letzten Zahl ("kpm1234_" bei "kpm1234_05") und meldet
+    # Lücken zwischen kleinster und größter Nummer sowie mehrfach vorkommende Nummern.
+    # Liefert (fehlend, doppelt) als Listen von Indizes, natürlich sortiert
+    groups = {}
+    for track in tracks:
+        match = re.fullmatch(r'(.*?)(\d+)', track.index)
+        if match:
+            groups.setdefault(match.group(1), []).append(match.group(2))
+    missing = []
+    duplicates = []
+    for prefix in sorted(groups):
+        numbers = sorted(int(n) for n in groups[prefix])
+        width = min(len(n) for n in groups[prefix])
+        present = set(numbers)
+        missing += [prefix + str(n).zfill(width) for n in range(numbers[0], numbers[-1] + 1) if n not in present]
+        duplicates += [prefix + str(n).zfill(width) for n in sorted(present) if numbers.count(n) > 1]
+    return missing, duplicates
+
+def format_rename_template(template: str, track, ext: str):
+    # Leerzeichen werden wieder zu Unterstrichen, damit der neue Name erneut geparst werden kann
+    def clean(value):
+        return value.replace(' ', '_').replace('/', '-').replace('\\', '-')
+    return template.format(index=clean(track.raw_index), titel=clean(track.title),
+                           kuenstler=clean(track.artist), labelcode=clean(track.label_code), ext=ext)
+
+def plan_renames(input_file, label_dict, template, options=None):
+    # Probelauf: (alter Pfad, neuer Pfad, Problem oder None) für jede Audiodatei aus der Textdatei
+    directory = os.path.dirname(input_file)
+    plan = []
+    targets = set()
+    with open(input_file, 'r', encoding='utf-8-sig') as infile:
+        for line in infile:
+            filename = line.split(';', 1)[0].strip()
+            if not filename:
+                continue
+            track = parse_track_info(filename, label_dict, options)
+            ext = filename.rsplit('.', 1)[1] if '.' in filename else ''
+            new_name = format_rename_template(template, track, ext)
+            old_path = os.path.join(directory, filename)
+            new_path = os.path.join(directory, new_name)
+            if new_name == filename:
+                continue
+            if not os.path.exists(old_path):
+                problem = "nicht_gefunden"
+            elif new_path in targets:
+                problem = "doppelter_name"
+            elif os.path.exists(new_path):
+                problem = "ziel_existiert"
+            else:
+                problem = None
+            targets.add(new_path)
+            plan.append((old_path, new_path, problem))
+    return plan
+
+def tracks_without_audio(input_file, tracks):
+    # Tracks aus der Textdatei, zu denen im selben Ordner keine Audiodatei liegt (Groß-/Kleinschreibung egal)
+    directory = os.path.dirname(input_file) or '.'
+    present = {name.lower() for name in os.listdir(directory)}
+    return [track for track in tracks if track.filename and track.filename.lower() not in present]
+
+def apply_renames(input_file, plan):
+    # Benennt nur die problemlosen Einträge um und trägt die neuen Namen in der Textdatei ein
+    renamed = {}
+    for old_path, new_path, problem in plan:
+        if problem is None:
+            os.rename(old_path, new_path)
+            renamed[os.path.basename(old_path)] = os.path.basename(new_path)
+    if not renamed:
+        return 0
+    with open(input_file, 'r', encoding='utf-8-sig') as infile:
+        lines = infile.readlines()
+    with open(input_file, 'w', encoding='utf-8') as outfile:
+        for line in lines:
+            parts = line.split(';', 1)
+            filename = parts[0].strip()
+            if filename in renamed:
+                line = renamed[filename] + (';' + parts[1] if len(parts) > 1 else '\n')
+            outfile.write(line)
+    return len(renamed)
+
+def describe_text_file(input_file, language=Language.DE, sample_bytes=1024 * 1024):
+    # Kodierung und Zeilenenden für die Anzeige, z.B. "UTF-8, CRLF"; liest höchstens sample_bytes
+    try:
+        with open(input_file, 'rb') as f:
+            data = f.read(sample_bytes)
+    except OSError:
+        return ""
+    if data.startswith(codecs.BOM_UTF8):
+        encoding = "UTF-8 BOM"
+    else:
+        try:
+            # Inkrementell, damit ein am Ende abgeschnittenes Zeichen nicht als Fehler zählt
+            codecs.getincrementaldecoder('utf-8')().decode(data, final=False)
+            encoding = "ASCII" if data.isascii() else "UTF-8"
+        except UnicodeDecodeError:
+            encoding = tr("encoding_not_utf8", language)
+    crlf = data.count(b'\r\n')
+    endings = [name for name, count in [("CRLF", crlf), ("LF", data.count(b'\n') - crlf),
+                                        ("CR", data.count(b'\r') - crlf)] if count]
+    if len(endings) > 1:
+        line_endings = tr("line_endings_mixed", language, endings="/".join(endings))
+    else:
+        line_endings = endings[0] if endings else "-"
+    return f"{encoding}, {line_endings}"
+
+WINDOWS_MAX_PATH = 260
+EXTENDED_PREFIX = '\\\\?\\'
+EXTENDED_UNC_PREFIX = EXTENDED_PREFIX + 'UNC\\'
+
+def long_path(path: str, windows=None):
+    # Unter Windows scheitert open() an Pfaden ab MAX_PATH Zeichen, z.B. tief in Netzlaufwerken. Solche Pfade
+    # bekommen für Dateizugriffe das Präfix \\?\, Freigaben (\\server\share\...) werden zu \\?\UNC\server\share\...
+    # Angezeigt wird weiter der ursprüngliche Pfad; andere Systeme und kurze Pfade bleiben unverändert
+    if not (os.name == 'nt' if windows is None else windows) or path.startswith(EXTENDED_PREFIX):
+        return path
+    path = ntpath.abspath(path)
+    if len(path) < WINDOWS_MAX_PATH:
+        return path
+    if path.startswith('\\\\'):
+        return EXTENDED_UNC_PREFIX + path[2:]
+    return EXTENDED_PREFIX + path
+
+def plain_path(path: str):
+    # Umkehrung von long_path für Anzeige und Vergleich
+    if path.startswith(EXTENDED_UNC_PREFIX):
+        return '\\\\' + path[len(EXTENDED_UNC_PREFIX):]
+    if path.startswith(EXTENDED_PREFIX):
+        return path[len(EXTENDED_PREFIX):]
+    return path
+
+def canonical_path(path: str):
+    # Dialog, Drag & Drop und Muster liefern dieselbe Datei evtl. unterschiedlich geschrieben
+    # (relativ, andere Trenner, Symlinks). Nicht vorhandene Dateien bleiben unverändert.
+    # realpath kann unter Windows \\?\ voranstellen; das Präfix gehört nicht in die Dateiliste
+    if not os.path.exists(long_path(path)):
+        return path
+    return plain_path(os.path.realpath(long_path(path)))
+
+INPUT_EXTENSIONS = ('.txt', '.cue')
+
+def scan_folder(directory):
+    # Liefert (txt- und cue-Dateien, Anzahl übergangener anderer Dateien)
     files = []
+    ignored = 0
     for root, dirs, filenames in os.walk(directory):
         for fn in filenames:
-            if fn.lower().endswith('.txt'):
+            if fn.lower().endswith(INPUT_EXTENSIONS):
                 files.append(os.path.join(root, fn))
+            else:
+                ignored += 1
+    return files, ignored
+
+def list_txt_files_in_dir(directory):
+    return scan_folder(directory)[0]
+
+def poll_hotfolder(directory, state):
+    # state: Pfad -> ((Größe, Änderungszeit), verarbeitet) aus dem letzten Durchlauf. Eine neue oder geänderte
+    # Datei wird erst geliefert, wenn sie beim nächsten Durchlauf unverändert ist, also fertig geschrieben wurde
+    ready = []
+    for path in list_txt_files_in_dir(directory):
+        try:
+            stat = os.stat(path)
+        except OSError:
+            continue
+        signature = (stat.st_size, stat.st_mtime_ns)
+        previous = state.get(path)
+        if previous is None or previous[0] != signature:
+            state[path] = (signature, False)
+        elif not previous[1]:
+            state[path] = (signature, True)
+            ready.append(path)
+    return ready
+
+def check_input_file(input_file, language=Language.DE):
+    # Liefert eine verständliche Meldung, wenn die Datei nicht verarbeitet werden kann
+    if not os.path.exists(long_path(input_file)):
+        return tr("file_not_found", language, path=input_file)
+    if not os.access(long_path(input_file), os.R_OK):
+        return tr("file_not_readable", language, path=input_file)
+    if os.path.getsize(long_path(input_file)) == 0:
+        return tr("file_empty", language, path=input_file)
+    return None
+
+def read_csv_header(csv_file, delimiter=';'):
+    # Liefert die Kopfzeile einer bestehenden CSV oder None, wenn die Datei fehlt oder leer ist
+    if not os.path.exists(long_path(csv_file)) or os.path.getsize(long_path(csv_file)) == 0:
+        return None
+    with open(long_path(csv_file), 'r', newline='', encoding='utf-8') as f:
+        reader = csv.reader(f, delimiter=delimiter)
+        return next(reader, None)
+
+def count_csv_rows(csv_file, delimiter=';'):
+    # Anzahl der Datenzeilen ohne Kopfzeile
+    with open(long_path(csv_file), 'r', newline='', encoding='utf-8', errors='replace') as f:
+        return max(sum(1 for _ in csv.reader(f, delimiter=delimiter)) - 1, 0)
+
+# Fehler verhindern die Auswertung einer Datei, alle anderen Typen sind Warnungen zu einzelnen Zeilen oder Tracks
+ERROR_SEVERITY = {"datei": "fehler", "keine_tracks": "fehler", "pflichtfeld": "fehler"}
+
+def error_severity(error):
+    return ERROR_SEVERITY.get(error["typ"], "warnung")
+
+def ensure_extension(path, extensions):
+    # Fehlt die Endung oder passt sie zu keinem der Formate, wird die erste angehängt
+    if os.path.splitext(path)[1].lower() in [e.lower() for e in extensions]:
+        return path
+    return path + extensions[0]
+
+def export_errors(error_file, file_errors):
+    # file_errors: Pfad -> Liste der Fehler aus parse_text_file/process_single_file
+    with open(error_file, 'w', newline='', encoding='utf-8') as f:
+        writer = csv.writer(f, delimiter=';')
+        writer.writerow(["Datei", "Zeile", "Typ", "Inhalt", "Schwere"])
+        for path, errors in file_errors.items():
+            for error in errors:
+                writer.writerow([path, error["zeile"] if error["zeile"] is not None else "", error["typ"],
+                                 error["inhalt"], error_severity(error)])
+
+def write_json_report(report_file, report):
+    with open(report_file, 'w', encoding='utf-8') as f:
+        json.dump(report, f, indent=2, ensure_ascii=False)
+
+@dataclass
+class ParseResult:
+    tracks: dict = field(default_factory=dict)  # TrackInfo -> Dauer in Sekunden
+    lines_read: int = 0
+    ignored_no_semicolon: int = 0
+    ignored_no_duration: int = 0
+    ignored_general: int = 0
+    errors: list = field(default_factory=list)
+    collapsed: list = field(default_factory=list)  # (Dateiname, zusammengeführt mit Dateiname)
+    placeholder_tracks: set = field(default_factory=set)  # Tracks, deren Dauer (teilweise) ein Platzhalter ist
+    format_problem: str = ''  # "vertauscht", "abwechselnd" oder "keine_dauern", siehe detect_format_problem
+    layout: str = ''  # Erkannter Aufbau der Datei, siehe detect_text_layout
+    header: str = ''  # Übersprungene Kopfzeile
+
+ENTRY_DELIMITER_CANDIDATES = [';', '\t', '|', ',']
+
+def detect_entry_delimiter(lines, configured='', sample_size=5):
+    # Einzeilige Einträge "index;titel;kuenstler;dauer": die ersten Zeilen müssen
+    # mit demselben Trennzeichen genau vier Felder ergeben, sonst gilt das alte Format
+    sample = lines[:sample_size]
+    if not sample:
+        return None
+    candidates = [configured] if configured else ENTRY_DELIMITER_CANDIDATES
+    for delimiter in candidates:
+        if all(len(line.split(delimiter)) == 4 for line in sample):
+            return delimiter
+    return None
+
+DURATION_PATTERN = re.compile(r'\d+([:.,]\d+)+')
+
+def looks_like_duration(value: str):
+    return bool(DURATION_PATTERN.fullmatch(value.strip()))
+
+def detect_format_problem(lines, min_lines=2):
+    # Prüft, ob die Spalte hinter dem Semikolon überhaupt nach Dauern aussieht.
+    # Liefert einen Hinweis auf die vermutlich gemeinte Anordnung oder '' wenn alles plausibel ist
+    if len(lines) < min_lines:
+        return ''
+    pairs = [line.split(';', 1) for line in lines if ';' in line]
+    if len(pairs) < len(lines) / 2:
+        # Kaum Semikolons: Dateiname und Dauer stehen evtl. abwechselnd in eigenen Zeilen.
+        # Die Dauer muss die ganze Zeile sein, ein Titel wie "PART 1: 10:30 MIX" zählt nicht;
+        # stehen in allen Zeilen Dauern, ist es kein Wechsel
+        names, durations = lines[0::2], lines[1::2]
+        if (sum(looks_like_duration(line) for line in durations) > len(durations) / 2
+                and sum(looks_like_duration(line) for line in names) <= len(names) / 2):
+            return "abwechselnd"
+        return ''
+    durations = [second for _, second in pairs if second.strip()]
+    if not durations or sum(looks_like_duration(d) for d in durations) >= len(durations) / 2:
+        return ''
+    if sum(looks_like_duration(first) for first, _ in pairs) > len(pairs) / 2:
+        return "vertauscht"
+    return "keine_dauern"
+
+# Aufbau einer Textdatei: "dateiname_dauer" (eine Zeile "Dateiname;Dauer" pro Track),
+# "einzeilig" ("index;titel;kuenstler;dauer") oder eines der Format-Probleme, die abgelehnt werden
+FORMAT_PROBLEMS = ["vertauscht", "abwechselnd", "keine_dauern"]
+
+def detect_text_layout(lines, configured_delimiter=''):
+    if detect_entry_delimiter(lines, configured_delimiter):
+        return "einzeilig"
+    return detect_format_problem(lines) or "dateiname_dauer"
+
+def looks_like_header(first: str, second: str):
+    # Offensichtliche Kopfzeile wie "Titel;Dauer": keine Ziffern, die Zeile darunter enthält eine Dauer
+    return (not any(ch.isdigit() for ch in first)
+            and any(looks_like_duration(part) for part in re.split(r'[;\t|,]', second)))
+
+def delimited_track_info(index: str, title: str, artist: str, label_dict: dict, options=None):
+    options = options or ParseOptions()
+    index, title, artist = (clean_field(f, options, name) for f, name in zip((index, title, artist), FIELD_NAMES))
+    idx = index.lower()
+    if options.ignore_index_leading_zeros:
+        idx = strip_index_leading_zeros(idx)
+    return TrackInfo(idx, title.lower(), artist.lower(), find_label_code(idx, label_dict), index,
+                     raw_title=title, raw_artist=artist, track_number=index_number(index))
+
+# Größere Dateien werden zeilenweise gelesen statt komplett in den Speicher;
+# die Format-Erkennung sieht dann nur die ersten FORMAT_SAMPLE_SIZE Zeilen
+STREAM_THRESHOLD = 5 * 1024 * 1024
+FORMAT_SAMPLE_SIZE = 200
+
+def iter_text_lines(input_file):
+    # (Zeilennummer, Zeile) aller nicht leeren Zeilen
+    with open(long_path(input_file), 'r', encoding='utf-8-sig') as infile:
+        yield from iter_string_lines(infile)
+
+def iter_string_lines(lines):
+    # Wie iter_text_lines für Text im Speicher (str) oder eine bereits geöffnete Datei
+    if isinstance(lines, str):
+        lines = lines.removeprefix('\ufeff').splitlines()
+    for line_num, line in enumerate(lines, start=1):
+        line = line.strip()
+        if line:
+            yield line_num, line
+
+CUE_FRAMES_PER_SECOND = 75
+
+def cue_timestamp(value: str):
+    # "mm:ss:ff" eines Cue-Sheets (75 Frames pro Sekunde) in Sekunden, None bei ungültigem Wert
+    parts = value.strip().split(':')
+    if len(parts) != 3 or not all(p.isdigit() for p in parts):
+        return None
+    minutes, seconds, frames = (int(p) for p in parts)
+    if seconds > 59 or frames >= CUE_FRAMES_PER_SECOND:
+        return None
+    return minutes * 60 + seconds + frames / CUE_FRAMES_PER_SECOND
+
+def cue_value(rest: str):
+    # Wert hinter einem Cue-Befehl, mit oder ohne Anführungszeichen
+    match = re.match(r'\s*"([^"]*)"', rest)
+    return match.group(1) if match else rest.strip()
+
+def wav_length(path):
+    with wave.open(path, 'rb') as audio:
+        return audio.getnframes() / audio.getframerate()
+
+# Dateiendung (klein, ohne Punkt) -> Funktion, die die Länge in Sekunden liest; neue Formate hier eintragen
+AUDIO_LENGTH_READERS = {"wav": wav_length}
+
+def audio_format_supported(path):
+    return os.path.splitext(path)[1].lower().lstrip('.') in AUDIO_LENGTH_READERS
+
+def audio_length(path):
+    # Länge in Sekunden über den Leser zur Dateiendung; None mit protokolliertem Grund, wenn das Format
+    # nicht unterstützt wird oder die Datei fehlt bzw. beschädigt ist
+    extension = os.path.splitext(path)[1].lower().lstrip('.')
+    reader = AUDIO_LENGTH_READERS.get(extension)
+    if reader is None:
+        log_error(f"Audiodatei {path}: Länge für Format \"{extension}\" nicht lesbar "
+                  f"(unterstützt: {', '.join(AUDIO_LENGTH_READERS)}).")
+        return None
+    try:
+        return reader(path)
+    except (OSError, EOFError, wave.Error) as e:
+        log_error(f"Audiodatei {path}: Länge nicht lesbar ({e}).")
+        return None
+
+def parse_cue_file(input_file, label_dict, options=None):
+    # Cue-Sheet: TRACK-Nummer als Index, TITLE und PERFORMER (sonst der des Albums). Die Dauer ergibt sich aus
+    # dem Abstand der INDEX-01-Zeitpunkte, beim letzten Track einer Audiodatei aus deren Gesamtlänge
+    options = options or ParseOptions()
+    result = ParseResult(layout="cue")
+    default_duration = parse_duration(options.default_duration) if options.default_duration else None
+    directory = os.path.dirname(input_file)
+    entries = []
+    album_performer = ''
+    audio_file = ''
+    for line_num, line in iter_text_lines(input_file):
+        result.lines_read += 1
+        command, _, rest = line.partition(' ')
+        command = command.upper()
+        entry = entries[-1] if entries else None
+        if command == 'FILE':
+            # FILE "album.wav" WAVE; ohne Anführungszeichen steht der Typ nach dem letzten Leerzeichen
+            audio_file = cue_value(rest) if rest.strip().startswith('"') else rest.strip().rsplit(' ', 1)[0]
+        elif command == 'TRACK':
+            entries.append({"zeile": line_num, "nummer": rest.split()[0] if rest.split() else '', "titel": '',
+                            "kuenstler": None, "start": None, "datei": audio_file})
+        elif command == 'TITLE' and entry:
+            entry["titel"] = cue_value(rest)
+        elif command == 'PERFORMER':
+            if entry:
+                entry["kuenstler"] = cue_value(rest)
+            else:
+                album_performer = cue_value(rest)
+        elif command == 'INDEX' and entry and rest.split()[:1] == ['01']:
+            # INDEX 00 (Vorlauf) und weitere Indexpunkte innerhalb des Tracks zählen nicht
+            entry["start"] = cue_timestamp(rest.split()[1] if len(rest.split()) > 1 else '')
+
+    # Länge jeder Audiodatei einmal lesen: für den letzten Track und zum Abgleich mit den INDEX-Zeitpunkten
+    lengths = {name: audio_length(os.path.join(directory, name))
+               for name in dict.fromkeys(entry["datei"] for entry in entries) if name}
+    for position, entry in enumerate(entries):
+        following = entries[position + 1] if position + 1 < len(entries) else None
+        length = lengths.get(entry["datei"])
+        unsupported = False
+        if following and following["datei"] == entry["datei"]:
+            end = following["start"]
+        else:
+            unsupported = bool(entry["datei"]) and not audio_format_supported(entry["datei"])
+            end = length
+        start = entry["start"]
+        # Beginnt ein Track erst nach dem Ende seiner Audiodatei, passen Cue-Sheet und Datei nicht zusammen
+        # (falsche Datei oder anders geschnitten). Die Dauer aus den INDEX-Abständen bleibt, wird aber gemeldet
+        beyond_end = start is not None and length is not None and start >= length
+        if beyond_end:
+            result.errors.append({"zeile": entry["zeile"], "typ": "cue_laenge",
+                                  "inhalt": f"TRACK {entry['nummer']} (INDEX {format_seconds(start)}, "
+                                            f"{entry['datei']} {format_seconds(length)})"})
+            log_error(f"Datei {input_file}, Zeile {entry['zeile']}: TRACK {entry['nummer']} beginnt nach dem Ende "
+                      f"von {entry['datei']}.")
+        duration = seconds_to_duration(end - start) if start is not None and end is not None and end > start else None
+        is_placeholder = duration is None and default_duration is not None
+        if is_placeholder:
+            duration = default_duration
+        if duration is None:
+            result.ignored_no_duration += 1
+            # Bei nicht unterstütztem Audioformat klar benennen, warum die Länge fehlt
+            if unsupported:
+                result.errors.append({"zeile": entry["zeile"], "typ": "audioformat",
+                                      "inhalt": f"TRACK {entry['nummer']} ({entry['datei']})"})
+            elif not beyond_end:
+                result.errors.append({"zeile": entry["zeile"], "typ": "ungueltige_dauer",
+                                      "inhalt": f"TRACK {entry['nummer']}"})
+            log_error(f"Datei {input_file}, Zeile {entry['zeile']}: Keine Dauer für TRACK {entry['nummer']}.")
+            continue
+        artist = entry["kuenstler"] if entry["kuenstler"] is not None else album_performer
+        track = replace(delimited_track_info(entry["nummer"], entry["titel"], artist, label_dict, options),
+                        filename=entry["datei"])
+        while track in result.tracks:
+            track = replace(track, occurrence=track.occurrence + 1)
+        result.tracks[track] = duration
+        if is_placeholder:
+            result.placeholder_tracks.add(track)
+    return result
+
+def parse_text_file(input_file, label_dict, options=None):
+    options = options or ParseOptions()
+    if input_file.lower().endswith('.cue'):
+        return parse_cue_file(input_file, label_dict, options)
+    streamed = os.path.getsize(long_path(input_file)) > STREAM_THRESHOLD
+    return parse_text_lines(iter_text_lines(input_file), label_dict, options, input_file, streamed)
+
+def parse_text(text, label_dict, options=None, source="Zwischenablage"):
+    # Wie parse_text_file, aber für Text im Speicher (z.B. aus der Zwischenablage); source erscheint in den Meldungen
+    return parse_text_lines(iter_string_lines(text), label_dict, options or ParseOptions(), source)
+
+def parse_text_lines(lines, label_dict, options, input_file, streamed=False):
+    # lines: (Zeilennummer, Zeile) wie von iter_text_lines; input_file nur für die Meldungen.
+    # Bei streamed wird nur der Anfang für die Format-Erkennung vorab gelesen
+    result = ParseResult()
+    first_seen = {}
+    if streamed:
+        sample = list(islice(lines, FORMAT_SAMPLE_SIZE))
+    else:
+        sample = list(lines)
+    # Kopfzeile vor der Format-Erkennung entfernen
+    if sample and (options.header_line == "immer" or (options.header_line == "auto" and len(sample) > 1
+                                                       and looks_like_header(sample[0][1], sample[1][1]))):
+        result.header = sample.pop(0)[1]
+    lines = chain(sample, lines)
+    sample_lines = [line for _, line in sample]
+    result.layout = detect_text_layout(sample_lines, options.entry_delimiter)
+    default_duration = parse_duration(options.default_duration) if options.default_duration else None
+    if result.layout in FORMAT_PROBLEMS:
+        # Lieber ablehnen als unsinnige Tracks erzeugen
+        result.format_problem = result.layout
+        log_error(f"Datei {input_file}: Format nicht erkannt ({result.format_problem}).")
+        return result
+    delimiter = detect_entry_delimiter(sample_lines, options.entry_delimiter) if result.layout == "einzeilig" else None
+
+    for line_num, line in lines:
+        result.lines_read += 1
+        if delimiter:
+            fields = [f.strip() for f in line.split(delimiter)]
+            if len(fields) != 4:
+                result.ignored_general += 1
+                result.errors.append({"zeile": line_num, "typ": "unvollstaendig", "inhalt": line})
+                log_error(f"Datei {input_file}, Zeile {line_num}: Unvollständige Zeile.")
+                continue
+            track = delimited_track_info(fields[0], fields[1], fields[2], label_dict, options)
+            duration_str = fields[3]
+        else:
+            if ';' not in line and filename_duration(line.strip(), options) is None:
+                result.ignored_no_semicolon += 1
+                result.errors.append({"zeile": line_num, "typ": "kein_semikolon", "inhalt": line})
+                log_error(f"Datei {input_file}, Zeile {line_num}: Kein Semikolon.")
+                continue
+
+            # Ohne Semikolon steht die Dauer im Dateinamen
+            parts = line.split(';', 1) if ';' in line else [line, '']
+            if len(parts) < 2:
+                result.ignored_general += 1
+                result.errors.append({"zeile": line_num, "typ": "unvollstaendig", "inhalt": line})
+                log_error(f"Datei {input_file}, Zeile {line_num}: Unvollständige Zeile.")
+                continue
+            
+            filename = parts[0].strip()
+            # Von Hand korrigierte Dauer (Bearbeiten-Dialog) gilt für jede Zeile mit diesem Dateinamen
+            duration_str = options.overrides.get(filename, {}).get("duration") or parts[1].strip()
+            track = parse_track_info(filename, label_dict, options)
+
+        duration_in_seconds = parse_duration(duration_str)
+        if duration_in_seconds is None and not delimiter:
+            duration_in_seconds = filename_duration(filename, options)
+        is_placeholder = duration_in_seconds is None and default_duration is not None
+        if is_placeholder:
+            duration_in_seconds = default_duration
+        if duration_in_seconds is None:
+            result.ignored_no_duration += 1
+            result.errors.append({"zeile": line_num, "typ": duration_error_type(duration_str), "inhalt": duration_str})
+            log_error(f"Datei {input_file}, Zeile {line_num}: Ungültige Dauer -> '{duration_str}'")
+            continue
+        
+        if track in result.tracks and not options.merge_durations:
+            occurrence = 1
+            while replace(track, occurrence=occurrence) in result.tracks:
+                occurrence += 1
+            track = replace(track, occurrence=occurrence)
+            result.tracks[track] = duration_in_seconds
+        elif track in result.tracks:
+            existing = first_seen[track]
+            if existing.raw_index != track.raw_index:
+                result.collapsed.append((track.filename, existing.filename))
+                log_error(f"Datei {input_file}, Zeile {line_num}: '{track.filename}' "
+                          f"zusammengeführt mit '{existing.filename}'.")
+            result.tracks[track] = add_durations(result.tracks[track], duration_in_seconds)
+        else:
+            first_seen[track] = track
+            result.tracks[track] = duration_in_seconds
+        if is_placeholder:
+            result.placeholder_tracks.add(track)
+    return result
+
+def expand_pattern(pattern):
+    # Muster wie ./daten/*.txt auflösen; Ordner werden rekursiv nach .txt- und .cue-Dateien durchsucht
+    files = []
+    for path in sorted(glob.glob(os.path.expanduser(pattern), recursive=True)):
+        if os.path.isdir(path):
+            files.extend(list_txt_files_in_dir(path))
+        elif path.lower().endswith(INPUT_EXTENSIONS):
+            files.append(path)
     return files
 
-def process_single_file(input_file, output_dir, label_dict, csv_columns):
+def format_submission_id(position: int, settings: dict):
+    # settings: {"prefix": "SUB-", "width": 4, "start": 1} -> SUB-0001 für die erste Zeile
+    number = int(settings.get("start", 1)) + position - 1
+    return f"{settings.get('prefix', '')}{number:0{int(settings.get('width', 0))}d}"
+
+def write_fixed_width(output_file, csv_columns, rows, widths):
+    # Feste Spaltenbreiten für die GEMA-Schnittstelle: nur Spalten mit Breite in widths, mit Leerzeichen
+    # aufgefüllt und zu lange Werte abgeschnitten. Liefert die gekürzten Werte als (Spalte, Wert)
+    columns = [(i, c, int(widths[c])) for i, c in enumerate(csv_columns) if widths.get(c)]
+    truncated = []
+    with open(output_file, 'w', encoding='utf-8', newline='\r\n') as f:
+        for row in rows:
+            record = ""
+            for i, column, width in columns:
+                value = row[i]
+                if len(value) > width:
+                    truncated.append((column, value))
+                record += value[:width].ljust(width)
+            f.write(record + "\n")
+    return truncated
+
+# Anführungszeichen in der CSV: "minimal" nur wo nötig (bisheriges Verhalten), "alle", "keine"
+# (Trennzeichen werden dann mit \ maskiert) oder "nicht_numerisch"
+CSV_QUOTING = {
+    "minimal": csv.QUOTE_MINIMAL,
+    "alle": csv.QUOTE_ALL,
+    "keine": csv.QUOTE_NONE,
+    "nicht_numerisch": csv.QUOTE_NONNUMERIC,
+}
+
+# Ausgabeformat -> (Trennzeichen, Endung); TSV wird ohne Anführungszeichen geschrieben (tsv_quoting in der Config)
+EXPORT_FORMATS = {"csv": (";", ".csv"), "tsv": ("\t", ".tsv")}
+
+def write_template(output_file, header_row, csv_quoting="minimal"):
+    # Leere Vorlage zum Ausfüllen von Hand: nur die Kopfzeile, Trennzeichen nach der Endung (.tsv = Tabulator)
+    export_format = "tsv" if output_file.lower().endswith(".tsv") else "csv"
+    quoting = CSV_QUOTING.get(csv_quoting, csv.QUOTE_MINIMAL)
+    with open(output_file, 'w', newline='', encoding='utf-8') as f:
+        writer = csv.writer(f, delimiter=EXPORT_FORMATS[export_format][0], quoting=quoting,
+                            escapechar='\\' if quoting == csv.QUOTE_NONE else None)
+        writer.writerow(header_row)
+
+# Mapping von Spaltennamen zu Funktionen, um den Wert aus track oder total_seconds zu gewinnen
+def column_value(col_name, track, total_seconds, position, placeholder=False, options=None, submission_id=None,
+                 decimal_separator=","):
+    if col_name.lower() == "position":
+        # Laufende Nummer in Ausgabereihenfolge, unabhängig vom Index aus dem Dateinamen
+        return str(position)
+    elif col_name.lower() == "id":
+        return format_submission_id(position, submission_id or {})
+    elif col_name.lower() == "index":
+        return format_index_numbers(track.raw_index, options) if options else track.raw_index
+    elif col_name.lower() in ORIGINAL_CASE_FIELDS:
+        return track_text(track, col_name, options)
+    elif col_name.lower() == "labelcode":
+        return track.label_code
+    elif col_name.lower() == "dauer":
+        # Platzhalter-Dauern werden mit * markiert, damit sie nie als echte Werte durchgehen
+        suffix = "*" if placeholder else ""
+        return format_duration(total_seconds) + suffix
+    elif col_name.lower() == "tracknummer":
+        # Ganzzahl ohne führende Nullen, unabhängig von index_numbers
+        return str(track.track_number) if track.track_number is not None else ""
+    elif col_name.lower() == "sekunden":
+        # Echte Sekunden zum Weiterrechnen (2:30 -> 150); ganzzahlig, daher ohne Dezimaltrennzeichen
+        return str(duration_seconds(total_seconds))
+    elif col_name.lower() == "minuten":
+        # Dezimalminuten zum Weiterrechnen (2:30 -> 2,50), Dezimaltrennzeichen passend zur Tabellenkalkulation
+        return f"{duration_seconds(total_seconds) / 60:.2f}".replace(".", decimal_separator)
+    elif col_name.lower() == "dateiname":
+        return track.filename
+    elif col_name.lower() == "format":
+        return track.ext
+    elif col_name.lower() == "genre":
+        return track.genre
+    elif col_name.lower() == "jahr":
+        return track.year
+    else:
+        return ""  # Unbekannte Spalte
+
+def track_rows(result, csv_columns, options=None, submission_id=None, decimal_separator=",", first_position=1):
+    # Zeilen wie in der CSV, für Ausgaben ohne Datei (z.B. Zwischenablage)
+    return [[column_value(c, track, total_seconds, position, track in result.placeholder_tracks, options,
+                          submission_id, decimal_separator) for c in csv_columns]
+            for position, (track, total_seconds) in enumerate(result.tracks.items(), start=first_position)]
+
+def portal_text(rows, settings, header_row=None):
+    # Zum Einfügen ins GEMA-Onlineportal: eine Zeile je Track, Felder mit settings["separator"] (Tabulator)
+    # getrennt, ohne Anführungszeichen; Zeilenumbrüche und Tabulatoren in Werten werden zu Leerzeichen
+    separator = settings.get("separator", "\t")
+    lines = ([header_row] if header_row and settings.get("header", False) else []) + rows
+    return "".join(separator.join(' '.join(str(value).split()) for value in line) + "\n" for line in lines)
+
+def strict_problems(result, track_dict, required_fields, options=None, language=Language.DE):
+    # Was im strengen Modus vor dem Export behoben sein muss: jeder Fehler beim Einlesen, eine Datei ohne
+    # Tracks und jeder Track mit leerer Pflichtspalte (Spaltennamen wie in csv_columns)
+    problems = list(result.errors)
+    if not track_dict:
+        problems.append({"zeile": None, "typ": "keine_tracks", "inhalt": tr("no_tracks_extracted", language)})
+    for position, (track, total_seconds) in enumerate(track_dict.items(), start=1):
+        missing = [c for c in required_fields
+                   if not column_value(c, track, total_seconds, position, options=options).strip()]
+        if missing:
+            problems.append({"zeile": None, "typ": "pflichtfeld",
+                             "inhalt": tr("strict_missing_fields", language, track=track.raw_index or track.filename,
+                                          fields=", ".join(missing))})
+    return problems
+
+class ExportCancelled(Exception):
+    # Export auf Wunsch abgebrochen; die angefangene Ausgabe ist dann schon entfernt
+    pass
+
+PROGRESS_STEP = 500  # Zeilen zwischen zwei Fortschrittsmeldungen
+
+def discard_partial_output(output_file, previous_size):
+    # Neue Datei löschen, beim Anhängen auf die Größe vor dem Export zurückschneiden
+    if previous_size is None:
+        if os.path.exists(long_path(output_file)):
+            os.remove(long_path(output_file))
+    else:
+        with open(long_path(output_file), 'r+b') as f:
+            f.truncate(previous_size)
+
+def process_single_file(input_file, output_dir, label_dict, csv_columns, append_file=None,
+                        language=Language.DE, write_report=False, options=None, label_targets=None,
+                        csv_quoting="minimal", submission_id=None, decimal_separator=",", csv_headers=None,
+                        fixed_widths=None, snapshot=None, export_format="csv", segment_targets=None,
+                        progress=None, cancelled=None, strict=False, required_fields=None, text=None):
+    # progress(geschriebene Zeilen, Zeilen gesamt) meldet den Fortschritt beim Schreiben; liefert cancelled()
+    # True, wird das Schreiben abgebrochen, die angefangene Ausgabe entfernt und ExportCancelled ausgelöst.
+    # strict: nichts schreiben, solange strict_problems etwas meldet.
+    # text: Trackliste im Speicher statt einer Datei (z.B. aus der Zwischenablage); input_file ist dann nur ihr Name
     from logging_utils import log_error
     idx_title = artist_title = label_code_title = duration_title = None
 
-    # Mapping von Spaltennamen zu Funktionen, um den Wert aus key oder total_seconds zu gewinnen
-    # keys: (idx, title, artist, label_code)
-    def get_column_value(col_name, key_tuple, total_seconds):
-        idx, title, artist, label_code = key_tuple
-        if col_name.lower() == "index":
-            return idx
-        elif col_name.lower() == "titel":
-            return title
-        elif col_name.lower() == "künstler":
-            return artist
-        elif col_name.lower() == "labelcode":
-            return label_code
-        elif col_name.lower() == "dauer":
-            return format_duration(total_seconds)
-        else:
-            return ""  # Unbekannte Spalte
+    def get_column_value(col_name, track, total_seconds, position):
+        return column_value(col_name, track, total_seconds, position, track in result.placeholder_tracks, options,
+                            submission_id, decimal_separator)
 
-    track_dict = {}
-    lines_read = 0
-    lines_ignored_no_semicolon = 0
-    lines_ignored_no_duration = 0
-    lines_ignored_general = 0
+    # Rückgabe: (Zusammenfassung, Fehlerliste); Fehler auf Dateiebene haben keine Zeilennummer
+    problem = check_input_file(input_file, language) if text is None else None
+    if problem:
+        log_error(problem)
+        return problem, [{"zeile": None, "typ": "datei", "inhalt": problem}]
 
     try:
-        with open(input_file, 'r', encoding='utf-8') as infile:
-            for line_num, line in enumerate(infile, start=1):
-                line = line.strip()
-                if not line:
-                    continue
-                lines_read += 1
-                if ';' not in line:
-                    lines_ignored_no_semicolon += 1
-                    log_error(f"Datei {input_file}, Zeile {line_num}: Kein Semikolon.")
-                    continue
-
-                parts = line.split(';', 1)
-                if len(parts) < 2:
-                    lines_ignored_general += 1
-                    log_error(f"Datei {input_file}, Zeile {line_num}: Unvollständige Zeile.")
-                    continue
-                
-                filename = parts[0].strip()
-                duration_str = parts[1].strip()
-
-                idx, title, artist = parse_track_filename(filename)
-                duration_in_seconds = parse_duration(duration_str)
-                if duration_in_seconds is None:
-                    lines_ignored_no_duration += 1
-                    log_error(f"Datei {input_file}, Zeile {line_num}: Ungültige Dauer -> '{duration_str}'")
-                    continue
-                
-                label_code = find_label_code(idx, label_dict)
-
-                key = (idx, title, artist, label_code)
-                if key in track_dict:
-                    track_dict[key] += duration_in_seconds
-                else:
-                    track_dict[key] = duration_in_seconds
+        if text is None:
+            result = parse_text_file(input_file, label_dict, options)
+        else:
+            result = parse_text(text, label_dict, options, input_file)
+        if result.format_problem:
+            message = tr("format_" + result.format_problem, language, path=input_file)
+            return message, [{"zeile": None, "typ": "datei", "inhalt": message}]
+        track_dict = result.tracks
+        text_only = []
+        # Ohne Datei (text) gibt es keinen Ordner, in dem die Audiodateien liegen könnten
+        if options and options.text_only_tracks != "behalten" and text is None:
+            text_only = tracks_without_audio(input_file, track_dict)
+            if options.text_only_tracks == "ausschliessen":
+                excluded = set(text_only)
+                track_dict = {track: seconds for track, seconds in track_dict.items() if track not in excluded}
+        if strict:
+            problems = strict_problems(result, track_dict, required_fields or [], options, language)
+            if problems:
+                message = tr("strict_refused", language, path=input_file, count=len(problems))
+                log_error(message)
+                return message, [{"zeile": None, "typ": "datei", "inhalt": message}] + problems
+        delimiter, extension = EXPORT_FORMATS.get(export_format, EXPORT_FORMATS["csv"])
+        # Überschrift je Spalte frei wählbar, die Zuordnung der Werte bleibt am Spaltennamen
+        header_row = [(csv_headers or {}).get(c) or c for c in csv_columns]
         
-        base_name = os.path.basename(input_file)
-        base_no_ext = remove_extension(base_name)
-        output_file = os.path.join(output_dir, f"output_{base_no_ext}.csv")
+        header_warning = ""
+        if append_file:
+            output_file = append_file
+            try:
+                existing_header = read_csv_header(output_file, delimiter)
+            except UnicodeDecodeError:
+                existing_header = None
+                header_warning = tr("warn_not_utf8", language)
+            if existing_header is not None and existing_header != header_row:
+                header_warning = tr("warn_columns_differ", language, columns=';'.join(existing_header))
+            write_header = not os.path.exists(long_path(output_file)) or os.path.getsize(long_path(output_file)) == 0
+            # Beim Anhängen weiterzählen, damit die Positionen über alle Dateien eindeutig bleiben
+            first_position = 1 if write_header else count_csv_rows(output_file, delimiter) + 1
+            mode = 'a'
+        else:
+            base_name = os.path.basename(input_file)
+            base_no_ext = remove_extension(base_name)
+            output_file = os.path.join(output_dir, f"output_{base_no_ext}{extension}")
+            write_header = True
+            first_position = 1
+            mode = 'w'
+        if header_warning:
+            log_error(f"Datei {output_file}: {header_warning.strip()}")
+        
+        exists = os.path.exists(long_path(output_file))
+        previous_size = os.path.getsize(long_path(output_file)) if mode == 'a' and exists else None
+        try:
+            with open(long_path(output_file), mode, newline='', encoding='utf-8') as outfile:
+                quoting = CSV_QUOTING.get(csv_quoting, csv.QUOTE_MINIMAL)
+                writer = csv.writer(outfile, delimiter=delimiter, quoting=quoting,
+                                    escapechar='\\' if quoting == csv.QUOTE_NONE else None)
+                if write_header:
+                    writer.writerow(header_row)  # Spalten aus der Config, ggf. mit eigenen Überschriften
+                rows = []
+                for position, (track, total_seconds) in enumerate(track_dict.items(), start=first_position):
+                    if cancelled and cancelled():
+                        raise ExportCancelled(output_file)
+                    row = [get_column_value(c, track, total_seconds, position) for c in csv_columns]
+                    writer.writerow(row)
+                    rows.append(row)
+                    if progress and len(rows) % PROGRESS_STEP == 0:
+                        progress(len(rows), len(track_dict))
+        except ExportCancelled:
+            discard_partial_output(output_file, previous_size)
+            log_error(f"Datei {output_file}: Export abgebrochen, angefangene Ausgabe entfernt.")
+            raise
+        
+        truncated = []
+        if fixed_widths:
+            base_no_ext = remove_extension(os.path.basename(input_file))
+            fixed_file = os.path.join(os.path.dirname(output_file), f"output_{base_no_ext}_fest.txt")
+            truncated = write_fixed_width(fixed_file, csv_columns, rows, fixed_widths)
+            for column, value in truncated:
+                log_error(f"Datei {fixed_file}: Spalte {column} gekürzt: '{value}'")
+        
+        totals = label_code_totals(track_dict)
+        target_results = check_label_code_targets(totals, label_targets or {})
+        segment_results = check_segment_targets(track_dict, segment_targets or [])
+        if options and options.check_index_sequence:
+            missing_indexes, duplicate_indexes = check_index_sequence(track_dict)
+        else:
+            missing_indexes = duplicate_indexes = []
         
-        with open(output_file, 'w', newline='', encoding='utf-8') as outfile:
-            writer = csv.writer(outfile, delimiter=';')
-            writer.writerow(csv_columns)  # Spalten aus der Config
-            for k, total_seconds in track_dict.items():
-                row = [get_column_value(c, k, total_seconds) for c in csv_columns]
-                writer.writerow(row)
+        if write_report:
+            base_no_ext = remove_extension(os.path.basename(input_file))
+            report_file = os.path.join(os.path.dirname(output_file), f"output_{base_no_ext}_report.json")
+            total_seconds = sum(duration_seconds(seconds) for seconds in track_dict.values())
+            write_json_report(report_file, {
+                "eingabe": input_file,
+                "ausgabe": output_file,
+                "zeitstempel": datetime.now().isoformat(timespec='seconds'),
+                "gelesene_zeilen": result.lines_read,
+                "tracks": len(track_dict),
+                "fehler_anzahl": len(result.errors),
+                "fehler": result.errors,
+                "zusammengefuehrt": [{"dateiname": filename, "mit": other}
+                                     for filename, other in result.collapsed],
+                "gesamtdauer_sekunden": total_seconds,
+                "gesamtdauer": format_seconds(total_seconds),
+                "labelcodes": {code: format_seconds(seconds) for code, seconds in totals.items()},
+                "index_fehlt": missing_indexes,
+                "index_doppelt": duplicate_indexes,
+                "labelcode_ziele": [{"labelcode": code, "dauer": format_seconds(actual),
+                                     "ziel": format_seconds(target), "erfuellt": ok}
+                                    for code, actual, target, ok in target_results],
+                "abschnitte": [{"von": start, "bis": end, "dauer": format_seconds(actual),
+                                "ziel": format_seconds(target), "erfuellt": ok}
+                               for start, end, actual, target, ok in segment_results],
+            })
         
-        summary = (f"Datei '{input_file}':\n"
-                   f"  Gelesene Zeilen: {lines_read}\n"
-                   f"  Ignoriert (kein Semikolon): {lines_ignored_no_semicolon}\n"
-                   f"  Ignoriert (ungültige Dauer): {lines_ignored_no_duration}\n"
-                   f"  Ignoriert (allg. Fehler): {lines_ignored_general}\n"
-                   f"  Ausgabe: {output_file}")
+        target_lines = "".join(
+            tr("label_target_ok" if ok else "label_target_failed", language, code=code,
+               actual=format_seconds(actual), target=format_seconds(target))
+            for code, actual, target, ok in target_results)
+        target_lines += "".join(
+            tr("segment_target_ok" if ok else "segment_target_failed", language, start=start, end=end,
+               actual=format_seconds(actual), target=format_seconds(target))
+            for start, end, actual, target, ok in segment_results)
+        summary = (tr("summary", language, path=input_file, read=result.lines_read,
+                      no_semicolon=result.ignored_no_semicolon, no_duration=result.ignored_no_duration,
+                      general=result.ignored_general)
+                   + (tr("summary_header", language, header=result.header) if result.header else "")
+                   + (tr("summary_placeholders", language, count=len(result.placeholder_tracks))
+                      if result.placeholder_tracks else "")
+                   + (tr("summary_text_only_" + options.text_only_tracks, language, count=len(text_only))
+                      if text_only else "")
+                   + "".join(tr("collapsed_duplicate", language, filename=filename, other=other)
+                             for filename, other in result.collapsed)
+                   + target_lines
+                   + (tr("index_missing", language, indexes=", ".join(missing_indexes)) if missing_indexes else "")
+                   + (tr("index_duplicate", language, indexes=", ".join(duplicate_indexes))
+                      if duplicate_indexes else "")
+                   + header_warning
+                   + (tr("fixed_width_truncated", language, count=len(truncated),
+                         columns=", ".join(sorted({column for column, _ in truncated}))) if truncated else "")
+                   + tr("summary_output", language, path=output_file))
         
         log_error(summary)
-        return summary
+        log_debug(f"Datei {input_file}: {len(track_dict)} Tracks nach {output_file} geschrieben.")
+        if snapshot is not None:
+            # Für "Mit letztem Export vergleichen"
+            snapshot[input_file] = snapshot_rows(track_dict, options)
+        if not track_dict:
+            # Sonst bliebe eine leere Ausgabe ohne jeden Hinweis
+            return summary, result.errors + [{"zeile": None, "typ": "keine_tracks",
+                                              "inhalt": tr("no_tracks_extracted", language)}]
+        missing_label = dict.fromkeys(track.raw_index or track.index for track in track_dict if not track.label_code)
+        missing_audio = dict.fromkeys(track.filename for track in text_only)
+        return summary, (result.errors + [{"zeile": None, "typ": "kein_labelcode", "inhalt": index}
+                                          for index in missing_label]
+                         + [{"zeile": None, "typ": "kein_audiofile", "inhalt": filename} for filename in missing_audio]
+                         + [{"zeile": None, "typ": "abschnitt_dauer",
+                             "inhalt": f"{start}-{end}: {format_seconds(actual)} / {format_seconds(target)}"}
+                            for start, end, actual, target, ok in segment_results if not ok])
+    except ExportCancelled:
+        raise
     except Exception as e:
         log_error("Exception: " + str(e))
         log_error(traceback.format_exc())
-        return f"Fehler beim Verarbeiten von {input_file}: {e}"
+        message = tr("file_error", language, path=input_file, error=e)
+        return message, [{"zeile": None, "typ": "datei", "inhalt": message}]
 
@@ -0,0 +1,14 @@
+PERFORMER "The Band"
+TITLE "Night Sessions"
+FILE "album.wav" WAVE
+  TRACK 01 AUDIO
+    TITLE "Night Drive"
+    INDEX 01 00:00:00
+  TRACK 02 AUDIO
+    TITLE "Summer Rain"
+    PERFORMER "John Smith"
+    INDEX 00 03:28:00
+    INDEX 01 03:30:37
+  TRACK 03 AUDIO
+    TITLE "Last Call"
+    INDEX 01 05:00:00
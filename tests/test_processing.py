@@ -0,0 +1,1144 @@
+import json
+import os
+import shutil
+import tempfile
+import unittest
+import wave
+from unittest import mock
+
+import logging_utils
+import main
+from config import resolve_paths, load_toml_config, default_settings, DEFAULT_CONFIG
+
+from processing import (TrackInfo, ParseOptions, parse_track_filename, parse_text_file,
+                        load_labelcodes, find_label_code, process_single_file, expand_pattern,
+                        plan_renames, apply_renames, normalize_case, parse_duration, parse_track_info,
+                        register_extractor, FIELD_EXTRACTORS, canonical_path, describe_text_file, scan_folder,
+                        check_index_sequence, find_label_match, LabelMatch, looks_swapped,
+                        debug_parse_file, clean_field, valid_replacements,
+                        merge_labelcodes, error_severity, export_errors, label_code_aggregate,
+                        write_label_aggregate, detect_text_layout, ensure_extension, compare_snapshots,
+                        format_duration, read_labelcode_entries, save_labelcodes, format_index_numbers,
+                        poll_hotfolder, duration_error_type, track_rows, portal_text, export_transforms,
+                        write_template, check_segment_targets, parse_cue_file,
+                        column_value, bulk_replace, ExportCancelled, long_path, plain_path,
+                        label_code_totals, check_label_code_targets, format_seconds, parse_text,
+                        audio_length, AUDIO_LENGTH_READERS)
+
+FIXTURES = os.path.join(os.path.dirname(__file__), 'fixtures')
+
+def fixture(name):
+    return os.path.join(FIXTURES, name)
+
+SUMMER_RAIN = TrackInfo('kpm1234_05', 'summer rain', 'john smith', '2061', 'KPM1234_05')
+NIGHT_DRIVE = TrackInfo('jcm042_01', 'night drive', 'the band', '48897', 'JCM042_01')
+
+class ParseTrackFilenameTest(unittest.TestCase):
+    def test_index_title_artist(self):
+        self.assertEqual(parse_track_filename('KPM1234_05_SUMMER_RAIN_john_smith.wav'),
+                         ('kpm1234_05', 'summer rain', 'john smith'))
+
+    def test_artist_separator(self):
+        filename = 'KPM1234_05_SUMMER_RAIN_twenty_one_pilots.wav'
+        for separator, artist in [("_", "twenty_one_pilots"), ("-", "twenty-one-pilots")]:
+            track = parse_track_info(filename, {}, ParseOptions(artist_separator=separator))
+            self.assertEqual((track.title, track.artist), ("summer rain", artist))
+
+    def test_digits_inside_title_stay_in_title(self):
+        self.assertEqual(parse_track_filename('KPM1234_05_TRACK_2_THE_FUTURE_john_smith.wav'),
+                         ('kpm1234_05', 'track 2 the future', 'john smith'))
+        self.assertEqual(parse_track_filename('KPM1234_05_SONG_2_john.wav'), ('kpm1234_05', 'song 2', 'john'))
+        self.assertEqual(parse_track_filename('KPM1234_05_HITS_OF_1999_2pac.wav'),
+                         ('kpm1234_05', 'hits of 1999', '2pac'))
+
+    def test_index_ends_after_first_digit_token(self):
+        self.assertEqual(parse_track_filename('CD01_A1_MY_SONG_artist.wav'),
+                         ('cd01', 'a1 my song', 'artist'))
+
+    def test_configured_index_token_count(self):
+        options = ParseOptions(index_token_count=2)
+        self.assertEqual(parse_track_filename('CD01_A1_MY_SONG_artist.wav', options),
+                         ('cd01_a1', 'my song', 'artist'))
+
+    def test_dash_separator_ignored_by_default(self):
+        self.assertEqual(parse_track_filename('KPM1234-05-SUMMER-RAIN-john-smith.wav'),
+                         ('kpm1234-05-summer-rain-john-smith', '', ''))
+
+    def test_audio_extension(self):
+        self.assertEqual(parse_track_info('KPM1234_05_SUMMER_RAIN_john_smith.WAV', {}).ext, 'wav')
+        self.assertEqual(parse_track_info('KPM1234_05_SUMMER_RAIN_john_smith.flac', {}).ext, 'flac')
+        self.assertEqual(parse_track_info('KPM1234_05_SUMMER_RAIN_john_smith.doc', {}).ext, '')
+        self.assertEqual(parse_track_info('KPM1234_05_SUMMER_RAIN_john_smith', {}).ext, '')
+
+    def test_bracket_metadata(self):
+        options = ParseOptions(bracket_metadata=True)
+        track = parse_track_info('01_[POP]_MY_TITLE_the_artist_(2019).mp3', {}, options)
+        self.assertEqual((track.index, track.title, track.artist, track.genre, track.year),
+                         ('01', 'my title', 'the artist', 'POP', '2019'))
+
+    def test_bracket_metadata_disabled(self):
+        track = parse_track_info('01_[POP]_MY_TITLE_the_artist_(2019).mp3', {})
+        self.assertEqual((track.genre, track.year), ('', ''))
+        self.assertEqual(track.title, '[pop] my title')
+
+    def test_mixed_case_index(self):
+        label_dict = {'kpm': '2061'}
+        mixed = parse_track_info('KpM1234_05_SUMMER_RAIN_john.wav', label_dict)
+        lower = parse_track_info('kpm1234_05_SUMMER_RAIN_john.wav', label_dict)
+        self.assertEqual((mixed.index, mixed.raw_index, mixed.label_code), ('kpm1234_05', 'KpM1234_05', '2061'))
+        self.assertEqual(lower.raw_index, 'kpm1234_05')
+        self.assertEqual(mixed, lower)
+        self.assertEqual(column_value("Index", mixed, 1.3, 1), 'KpM1234_05')
+        self.assertEqual(find_label_code('kPM1234_05', label_dict), '2061')
+
+    def test_mixed_case_index_merged_with_first_casing(self):
+        with tempfile.TemporaryDirectory() as tmp:
+            path = os.path.join(tmp, 'liste.txt')
+            with open(path, 'w', encoding='utf-8') as f:
+                f.write('KPM1234_05_SUMMER_RAIN_john.wav;1:30\nkpm1234_05_SUMMER_RAIN_john.wav;0:45\n')
+            tracks = parse_text_file(path, {'kpm': '2061'}, ParseOptions(merge_durations=True)).tracks
+        self.assertEqual([(t.raw_index, t.label_code, d) for t, d in tracks.items()], [('KPM1234_05', '2061', 2.15)])
+
+    def test_multiple_separators(self):
+        options = ParseOptions(separators='_-.')
+        self.assertEqual(parse_track_filename('KPM1234-05.SUMMER_RAIN-john.smith.wav', options),
+                         ('kpm1234_05', 'summer rain', 'john smith'))
+
+class ParseDurationTest(unittest.TestCase):
+    def test_comma_matches_colon_and_dot(self):
+        for text in ['3:45', '3.45', '3,45']:
+            self.assertEqual(parse_duration(text), 3.45)
+
+    def test_comma_with_three_parts(self):
+        self.assertEqual(parse_duration('1,23,45'), parse_duration('1:23:45'))
+        self.assertEqual(parse_duration('1,23,45'), parse_duration('1.23.45'))
+
+    def test_comma_without_decimal_part(self):
+        self.assertIsNone(parse_duration('345'))
+        self.assertIsNone(parse_duration('3,ab'))
+
+    def test_leading_zero_hours(self):
+        self.assertEqual(parse_duration('00:03:45'), 3.45)
+        self.assertEqual(parse_duration('01:02:05'), 62.05)
+        self.assertIsNone(parse_duration('00:75:00'))
+
+    def test_negative_duration_is_rejected(self):
+        self.assertIsNone(parse_duration('-1:00'))
+        self.assertEqual(duration_error_type('-1:00'), "negative_dauer")
+        self.assertEqual(duration_error_type('abc'), "ungueltige_dauer")
+
+class CleanFieldTest(unittest.TestCase):
+    def test_default_only_collapses_whitespace(self):
+        self.assertEqual(clean_field('  summer   rain - ', ParseOptions()), 'summer rain -')
+
+    def test_strip_chars_and_replacements(self):
+        options = ParseOptions(strip_chars='-.', field_replacements=[(r'^\d+\s+', ''), (r'[()]', '')])
+        self.assertEqual(clean_field('01 summer rain (remix) -', options), 'summer rain remix')
+
+    def test_applied_after_parsing(self):
+        options = ParseOptions(separators='_', strip_chars='-')
+        track = parse_track_info('KPM1234_05_-_SUMMER_RAIN_-_john_smith_-.wav', {}, options)
+        self.assertEqual((track.title, track.artist), ('summer rain', 'john smith'))
+
+    def test_rules_per_field_in_order(self):
+        rules = [{"feld": "kuenstler", "suchen": "john smith", "ersetzen": "J. Smith"},
+                 {"feld": "index", "suchen": r"^KPM", "ersetzen": "", "regex": True},
+                 {"feld": "alle", "suchen": "J.", "ersetzen": "Jon"}]
+        options = ParseOptions(field_replacements=valid_replacements(rules))
+        track = parse_track_info('KPM1234_05_SUMMER_RAIN_john_smith.wav', {}, options)
+        self.assertEqual((track.raw_index, track.title, track.artist), ('1234_05', 'summer rain', 'jon smith'))
+
+    def test_invalid_patterns_are_skipped(self):
+        with tempfile.TemporaryDirectory() as tmp:
+            cwd = os.getcwd()
+            os.chdir(tmp)  # error.log landet im Temp-Ordner
+            self.addCleanup(os.chdir, cwd)
+            self.assertEqual(valid_replacements([['(', ''], ['x'], ['a', 'b']]), [('a', 'b')])
+
+    def test_debug_rows_show_before_and_after(self):
+        with tempfile.TemporaryDirectory() as tmp:
+            path = os.path.join(tmp, 'liste.txt')
+            with open(path, 'w', encoding='utf-8') as f:
+                f.write('KPM1234_05_SUMMER_RAIN_john_smith_-.wav;1.00\n')
+            row = debug_parse_file(path, {}, ParseOptions(strip_chars='-'))[0]
+        self.assertEqual(row["cleanup"], [("artist", "john smith -", "john smith")])
+
+class FieldExtractorTest(unittest.TestCase):
+    def test_configured_pattern_before_tokens(self):
+        options = ParseOptions(filename_patterns=[r'(?P<artist>[^-]+) - (?P<title>.+) \[(?P<index>\w+)\]'])
+        track = parse_track_info('John Smith - Summer_Rain [KPM1234].wav', {'kpm': '2061'}, options)
+        self.assertEqual((track.index, track.title, track.artist, track.raw_index),
+                         ('kpm1234', 'summer rain', 'john smith', 'KPM1234'))
+
+    def test_falls_back_to_tokens(self):
+        options = ParseOptions(filename_patterns=[r'(?P<index>\d+) (?P<title>.+)'])
+        track = parse_track_info('KPM1234_05_SUMMER_RAIN_john_smith.wav', {}, options)
+        self.assertEqual((track.index, track.title, track.artist), ('kpm1234_05', 'summer rain', 'john smith'))
+
+    def test_registered_extractor(self):
+        def reversed_extractor(filename, options):
+            if not filename.startswith('rev_'):
+                return None
+            return 'X1', 'title', 'artist'
+        register_extractor(reversed_extractor)
+        self.addCleanup(FIELD_EXTRACTORS.remove, reversed_extractor)
+        self.assertEqual(parse_track_info('rev_anything.wav', {}).index, 'x1')
+        self.assertEqual(parse_track_info('KPM1234_05_SUMMER_RAIN_john_smith.wav', {}).index, 'kpm1234_05')
+
+class NormalizeCaseTest(unittest.TestCase):
+    def test_particles_stay_lowercase(self):
+        self.assertEqual(normalize_case('john van dyke'), 'John van Dyke')
+        self.assertEqual(normalize_case('the end of the road'), 'The End of the Road')
+
+    def test_hyphenated_words(self):
+        self.assertEqual(normalize_case('jean-luc PONTY'), 'Jean-Luc Ponty')
+
+    def test_original_case_per_column(self):
+        track = parse_track_info('KPM1234_05_SUMMER_RAIN_John_McSmith.wav', {})
+        self.assertEqual((track.title, track.raw_title, track.raw_artist), ('summer rain', 'SUMMER RAIN', 'John McSmith'))
+        options = ParseOptions(normalize_case=True, original_case_columns=["Künstler"])
+        self.assertEqual([column_value(c, track, 3.3, 1, options=options) for c in ["Titel", "Künstler"]],
+                         ['Summer Rain', 'John McSmith'])
+
+class SwapHintTest(unittest.TestCase):
+    def test_artist_in_capitals(self):
+        self.assertTrue(looks_swapped('john smith', 'SUMMER RAIN'))
+        self.assertFalse(looks_swapped('SUMMER RAIN', 'john smith'))
+        self.assertFalse(looks_swapped('SUMMER RAIN', 'THE BAND'))
+
+    def test_debug_rows_flag_swaps_until_corrected(self):
+        options = ParseOptions(filename_patterns=[r'(?P<index>\w+)_(?P<title>[a-z_]+)_(?P<artist>[A-Z_]+)'])
+        with tempfile.TemporaryDirectory() as tmp:
+            path = os.path.join(tmp, 'liste.txt')
+            with open(path, 'w', encoding='utf-8') as f:
+                f.write('KPM1234_john_smith_SUMMER_RAIN.wav;1.00\n')
+            self.assertTrue(debug_parse_file(path, {}, options)[0]["swap_hint"])
+            options.overrides['KPM1234_john_smith_SUMMER_RAIN.wav'] = {"title": "summer rain", "artist": "john smith"}
+            self.assertFalse(debug_parse_file(path, {}, options)[0]["swap_hint"])
+
+class SegmentTargetsTest(unittest.TestCase):
+    def test_totals_per_index_range(self):
+        tracks = {TrackInfo('kpm_01', 'a', 'x'): 1.3, TrackInfo('kpm_02', 'b', 'x'): 2.15,
+                  TrackInfo('kpm_11', 'c', 'x'): 3.0, TrackInfo('ohne', 'd', 'x'): 9.0}
+        segments = [{"von": 1, "bis": 10, "ziel": "3:45"}, {"von": 11, "bis": 20, "ziel": "2:50", "toleranz": "0:10"},
+                    {"von": 21, "bis": 30, "ziel": "1:00"}]
+        results = [(start, end, format_seconds(actual), ok)
+                   for start, end, actual, _, ok in check_segment_targets(tracks, segments)]
+        self.assertEqual(results, [(1, 10, "3:45", True), (11, 20, "3:00", True), (21, 30, "0:00", False)])
+
+    def test_seconds_sum_past_one_minute(self):
+        tracks = {TrackInfo(f'kpm_{n:02d}', str(n), 'x'): parse_duration('3:45') for n in range(1, 11)}
+        [(_, _, actual, target, ok)] = check_segment_targets(tracks, [{"von": 1, "bis": 10, "ziel": "37:30"}])
+        self.assertEqual((format_seconds(actual), format_seconds(target), ok), ("37:30", "37:30", True))
+
+class IndexSequenceTest(unittest.TestCase):
+    def tracks(self, *indexes):
+        return [TrackInfo(index, '', '', occurrence=i) for i, index in enumerate(indexes)]
+
+    def test_gaps_and_duplicates(self):
+        missing, duplicates = check_index_sequence(self.tracks('001', '002', '005', '002', '010'))
+        self.assertEqual(missing, ['003', '004', '006', '007', '008', '009'])
+        self.assertEqual(duplicates, ['002'])
+
+    def test_grouped_by_prefix(self):
+        missing, duplicates = check_index_sequence(self.tracks('kpm1234_01', 'kpm1234_03', 'jcm042_01', 'intro'))
+        self.assertEqual(missing, ['kpm1234_02'])
+        self.assertEqual(duplicates, [])
+
+    def test_natural_order(self):
+        missing, _ = check_index_sequence(self.tracks('9', '11', '10'))
+        self.assertEqual(missing, [])
+
+class LabelCodeTest(unittest.TestCase):
+    def test_targets_sum_real_seconds(self):
+        tracks = {TrackInfo('kpm1', 'a', 'x', '2061'): parse_duration('2:45'),
+                  TrackInfo('kpm2', 'b', 'x', '2061'): parse_duration('0:30')}
+        totals = label_code_totals(tracks)
+        self.assertEqual(totals, {'2061': 195})
+        self.assertEqual(format_seconds(totals['2061']), '3:15')
+        self.assertEqual(check_label_code_targets(totals, {'2061': '3:00'}), [('2061', 195, 180, True)])
+
+    def test_totals_of_merged_durations(self):
+        with tempfile.TemporaryDirectory() as tmp:
+            path = os.path.join(tmp, 'liste.txt')
+            with open(path, 'w', encoding='utf-8') as f:
+                f.write('KPM1234_05_SUMMER_RAIN_john.wav;0:59\n' * 3 + 'KPM1234_06_NIGHT_DRIVE_john.wav;0:50\n')
+            tracks = parse_text_file(path, {'kpm': '2061'}, ParseOptions(merge_durations=True)).tracks
+        totals = label_code_totals(tracks)
+        self.assertEqual(totals, {'2061': 227})
+        self.assertEqual(check_label_code_targets(totals, {'2061': '3:45'}), [('2061', 227, 225, True)])
+
+    def test_mixed_case_index_matches(self):
+        label_dict = load_labelcodes(fixture('labelcodes.txt'))
+        self.assertEqual(find_label_code('Cd01', label_dict), '11111')
+        self.assertEqual(find_label_code('cD01', label_dict), '11111')
+        self.assertEqual(find_label_code('xyz', label_dict), '')
+
+    def test_match_reports_prefix(self):
+        label_dict = load_labelcodes(fixture('labelcodes.txt'))
+        self.assertEqual(find_label_match('KPM1234_05', label_dict), LabelMatch('2061', 'kpm'))
+        self.assertIsNone(find_label_match('xyz', label_dict))
+
+    def test_merge_files_later_file_wins(self):
+        main, extra = fixture('labelcodes.txt'), fixture('labelcodes_extra.txt')
+        label_dict, sources, conflicts = merge_labelcodes([main, extra])
+        self.assertEqual((label_dict['kpm'], label_dict['apm'], label_dict['jcm']), ('9999', '1234', '48897'))
+        self.assertEqual((sources['kpm'], sources['jcm']), (extra, main))
+        self.assertEqual(conflicts, [('kpm', '9999', extra, '2061', main)])
+
+    def test_json_shapes(self):
+        with tempfile.TemporaryDirectory() as tmp:
+            path = os.path.join(tmp, 'labelcodes.json')
+            for content in ['{"KPM": "2061", "JCM": 48897}',
+                            '[{"label": "KPM", "code": "2061"}, {"label": "JCM", "code": "48897"}]']:
+                with open(path, 'w', encoding='utf-8') as f:
+                    f.write(content)
+                self.assertEqual(read_labelcode_entries(path), [('KPM', '2061'), ('JCM', '48897')])
+            save_labelcodes(path, [('KPM', '2061')])
+            self.assertEqual(load_labelcodes(path), {'kpm': '2061'})
+
+    def test_duplicate_json_keys_last_wins(self):
+        with tempfile.TemporaryDirectory() as tmp:
+            path = os.path.join(tmp, 'labelcodes.json')
+            with open(path, 'w', encoding='utf-8') as f:
+                f.write('{"KPM": "2061", "JCM": "48897", "KPM": "9999"}')
+            label_dict, _, conflicts = merge_labelcodes([path])
+        self.assertEqual(label_dict, {'kpm': '9999', 'jcm': '48897'})
+        self.assertEqual(conflicts, [('kpm', '9999', path, '2061', path)])
+
+    @mock.patch('processing.log_error')
+    def test_unknown_json_shape_is_reported(self, log):
+        with tempfile.TemporaryDirectory() as tmp:
+            path = os.path.join(tmp, 'labelcodes.json')
+            with open(path, 'w', encoding='utf-8') as f:
+                f.write('[["KPM", "2061"]]')
+            self.assertEqual(read_labelcode_entries(path), [])
+        self.assertIn("unbekannte JSON-Form", log.call_args[0][0])
+
+    def test_longest_prefix_wins(self):
+        label_dict = {'kpm': '2061', 'kpm12': '99999', 'k': '1'}
+        self.assertEqual(find_label_match('kpm1234', label_dict), LabelMatch('99999', 'kpm12'))
+        self.assertEqual(find_label_match('kpm9', label_dict), LabelMatch('2061', 'kpm'))
+
+    def test_label_code_override(self):
+        options = ParseOptions(overrides={'KPM1234_05_SUMMER_RAIN_john_smith.wav': {"label_code": "99999"}})
+        track = parse_track_info('KPM1234_05_SUMMER_RAIN_john_smith.wav', {'kpm': '2061'}, options)
+        self.assertEqual(track.label_code, '99999')
+
+@mock.patch('processing.log_error')
+class ParseTextFileTest(unittest.TestCase):
+    def setUp(self):
+        self.label_dict = load_labelcodes(fixture('labelcodes.txt'))
+
+    def parse(self, name):
+        return parse_text_file(fixture(name), self.label_dict)
+
+    def test_simple_sums_repeated_tracks(self, _log):
+        result = parse_text_file(fixture('simple.txt'), self.label_dict, ParseOptions(merge_durations=True))
+        self.assertEqual(list(result.tracks), [SUMMER_RAIN, NIGHT_DRIVE])
+        self.assertAlmostEqual(result.tracks[SUMMER_RAIN], 2.15)
+        self.assertAlmostEqual(result.tracks[NIGHT_DRIVE], 2.15)
+        self.assertEqual(result.lines_read, 3)
+        self.assertEqual(result.errors, [])
+
+    def test_sums_in_real_seconds(self, _log):
+        with tempfile.TemporaryDirectory() as tmp:
+            path = os.path.join(tmp, 'liste.txt')
+            with open(path, 'w', encoding='utf-8') as f:
+                f.write('KPM1234_05_SUMMER_RAIN_john_smith.wav;0:59\n' * 3)
+            result = parse_text_file(path, {}, ParseOptions(merge_durations=True))
+        self.assertEqual([format_duration(d) for d in result.tracks.values()], ["2:57"])
+
+    def test_text_in_memory(self, _log):
+        with open(fixture('simple.txt'), encoding='utf-8') as f:
+            text = f.read()
+        self.assertEqual(parse_text(text, self.label_dict).tracks, self.parse('simple.txt').tracks)
+        result = parse_text('\ufeffKPM1234_05_SUMMER_RAIN_john_smith.wav;1:30\n\nohne semikolon', self.label_dict)
+        self.assertEqual([(t.raw_index, d) for t, d in result.tracks.items()], [('KPM1234_05', 1.3)])
+        self.assertEqual(result.errors, [{"zeile": 3, "typ": "kein_semikolon", "inhalt": "ohne semikolon"}])
+
+    def test_keep_repeated_tracks_separate(self, _log):
+        result = self.parse('simple.txt')
+        self.assertEqual(list(result.tracks.values()), [1.3, 2.15, 0.45])
+        self.assertEqual([track.occurrence for track in result.tracks], [0, 0, 1])
+
+    def test_duration_override(self, _log):
+        options = ParseOptions(overrides={'KPM1234_05_SUMMER_RAIN_john_smith.wav': {"duration": "2:05"}})
+        result = parse_text_file(fixture('crlf.txt'), self.label_dict, options)
+        self.assertEqual([format_duration(d) for d in result.tracks.values()], ["2:05", "2:15"])
+
+    def test_rows_independent_of_add_order(self, _log):
+        # Jede Datei wird für sich geparst; gemeinsame Optionen dürfen die Reihenfolge nicht durchreichen
+        names = ['simple.txt', 'filename_durations.txt', 'sample.cue', 'padded_index.txt']
+        options = ParseOptions(merge_durations=True, default_duration="2:00")
+        columns = ["Index", "Titel", "Künstler", "Labelcode", "Dauer"]
+
+        def rows_in_order(order):
+            return {name: track_rows(parse_text_file(fixture(name), self.label_dict, options), columns, options)
+                    for name in order}
+
+        self.assertEqual(rows_in_order(names), rows_in_order(reversed(names)))
+
+    def test_portal_text(self, _log):
+        result = self.parse('crlf.txt')
+        rows = track_rows(result, ["Position", "Titel", "Dauer"])
+        self.assertEqual(portal_text(rows, {"separator": "\t"}, ["Nr", "Titel", "Dauer"]),
+                         "1\tsummer rain\t1:30\n2\tnight drive\t2:15\n")
+        self.assertTrue(portal_text(rows, {"separator": "|", "header": True}, ["Nr", "Titel", "Dauer"])
+                        .startswith("Nr|Titel|Dauer\n1|summer rain|1:30\n"))
+
+    def test_export_transforms(self, _log):
+        with tempfile.TemporaryDirectory() as tmp:
+            path = os.path.join(tmp, 'liste.txt')
+            with open(path, 'w', encoding='utf-8') as f:
+                f.write("KPM1234_05_SUMMER_RAIN_john_smith.wav;2:456\n")
+            transforms = export_transforms(path, self.label_dict)
+        self.assertEqual([(kind, before, after) for kind, _, before, after in transforms],
+                         [("klein", "SUMMER RAIN", "summer rain"), ("gerundet", "2:456", "2:46")])
+
+    def test_crlf(self, _log):
+        result = self.parse('crlf.txt')
+        self.assertEqual(list(result.tracks), [SUMMER_RAIN, NIGHT_DRIVE])
+        self.assertAlmostEqual(result.tracks[SUMMER_RAIN], 1.3)
+
+    def test_bom_is_not_part_of_index(self, _log):
+        result = self.parse('bom.txt')
+        self.assertEqual(list(result.tracks), [SUMMER_RAIN])
+        self.assertEqual(list(result.tracks)[0].raw_index, 'KPM1234_05')
+
+    def test_invalid_lines_are_counted(self, _log):
+        result = self.parse('invalid_lines.txt')
+        self.assertEqual(len(result.tracks), 2)
+        self.assertEqual(result.lines_read, 4)
+        self.assertEqual(result.ignored_no_semicolon, 1)
+        self.assertEqual(result.ignored_no_duration, 1)
+        self.assertEqual([e["typ"] for e in result.errors], ["kein_semikolon", "ungueltige_dauer"])
+
+    def test_default_duration_for_invalid_lines(self, _log):
+        result = parse_text_file(fixture('invalid_lines.txt'), self.label_dict, ParseOptions(default_duration='2:00'))
+        self.assertEqual(len(result.tracks), 3)
+        self.assertEqual(result.ignored_no_duration, 0)
+        self.assertEqual([t.index for t in result.placeholder_tracks], ['jcm042_01'])
+        self.assertAlmostEqual(result.tracks[NIGHT_DRIVE], 2.0)
+
+    def test_mixed_case_index_keeps_raw_casing(self, _log):
+        result = parse_text_file(fixture('mixed_case_index.txt'), self.label_dict,
+                                 ParseOptions(merge_durations=True))
+        self.assertEqual(len(result.tracks), 1)
+        track = list(result.tracks)[0]
+        self.assertEqual(track.index, 'cd01')
+        self.assertEqual(track.raw_index, 'Cd01')
+        self.assertEqual(track.label_code, '11111')
+        self.assertAlmostEqual(result.tracks[track], 3.0)
+
+@mock.patch('processing.log_error')
+class HeaderLineTest(unittest.TestCase):
+    def setUp(self):
+        self.label_dict = load_labelcodes(fixture('labelcodes.txt'))
+
+    def test_header_detected(self, _log):
+        result = parse_text_file(fixture('header.txt'), self.label_dict)
+        self.assertEqual(result.header, 'Titel\tDauer')
+        self.assertEqual(list(result.tracks), [SUMMER_RAIN, NIGHT_DRIVE])
+        self.assertEqual(result.errors, [])
+
+    def test_header_kept_when_disabled(self, _log):
+        result = parse_text_file(fixture('header.txt'), self.label_dict, ParseOptions(header_line="nie"))
+        self.assertEqual(result.header, '')
+        self.assertEqual([e["typ"] for e in result.errors], ["kein_semikolon"])
+
+    def test_header_forced(self, _log):
+        result = parse_text_file(fixture('simple.txt'), self.label_dict, ParseOptions(header_line="immer"))
+        self.assertEqual(result.header, 'KPM1234_05_SUMMER_RAIN_john_smith.wav;1:30')
+        self.assertEqual(result.lines_read, 2)
+
+    def test_track_line_is_not_a_header(self, _log):
+        self.assertEqual(parse_text_file(fixture('simple.txt'), self.label_dict).header, '')
+
+@mock.patch('processing.log_error')
+class DelimitedEntriesTest(unittest.TestCase):
+    def setUp(self):
+        self.label_dict = load_labelcodes(fixture('labelcodes.txt'))
+
+    def test_semicolon_entries(self, _log):
+        result = parse_text_file(fixture('delimited.txt'), self.label_dict)
+        self.assertEqual(list(result.tracks)[:2], [SUMMER_RAIN, NIGHT_DRIVE])
+        self.assertEqual(len(result.tracks), 3)
+        self.assertAlmostEqual(result.tracks[NIGHT_DRIVE], 2.15)
+
+    def test_tab_entries_are_detected(self, _log):
+        result = parse_text_file(fixture('delimited_tab.txt'), self.label_dict)
+        self.assertEqual(list(result.tracks), [SUMMER_RAIN, NIGHT_DRIVE])
+
+    def test_configured_delimiter_must_match(self, _log):
+        result = parse_text_file(fixture('delimited_tab.txt'), self.label_dict, ParseOptions(entry_delimiter='|'))
+        self.assertEqual(result.tracks, {})
+        self.assertEqual(result.ignored_no_semicolon, 2)
+
+@mock.patch('processing.log_error')
+class FormatProblemTest(unittest.TestCase):
+    def test_swapped_columns_are_rejected(self, _log):
+        result = parse_text_file(fixture('duration_first.txt'), {})
+        self.assertEqual(result.format_problem, "vertauscht")
+        self.assertEqual(result.tracks, {})
+
+    def test_alternating_lines_are_rejected(self, _log):
+        result = parse_text_file(fixture('alternating.txt'), {})
+        self.assertEqual(result.format_problem, "abwechselnd")
+        self.assertEqual(result.tracks, {})
+
+    def test_single_invalid_duration_is_no_format_problem(self, _log):
+        self.assertEqual(parse_text_file(fixture('invalid_lines.txt'), {}).format_problem, '')
+
+class TextLayoutTest(unittest.TestCase):
+    def test_filename_and_duration(self):
+        self.assertEqual(detect_text_layout(['KPM1234_05_SUMMER_RAIN_john.wav;1:30',
+                                             'JCM042_01_NIGHT_DRIVE_band.wav;2:15']), "dateiname_dauer")
+
+    def test_titles_with_colons_stay_filename_and_duration(self):
+        self.assertEqual(detect_text_layout(['KPM_01_PART_1:_INTRO_john.wav;1:30',
+                                             'KPM_02_12:00_NOON_band.wav;2:15']), "dateiname_dauer")
+
+    def test_single_line_entries(self):
+        self.assertEqual(detect_text_layout(['KPM1;Summer Rain;John;1:30', 'KPM2;Night: Drive;Band;2:15']),
+                         "einzeilig")
+
+    def test_alternating(self):
+        self.assertEqual(detect_text_layout(['KPM_01_PART_1:_INTRO_john.wav', '1:30',
+                                             'KPM_02_NIGHT_DRIVE_band.wav', '2:15']), "abwechselnd")
+
+    def test_colon_titles_without_durations_are_not_alternating(self):
+        self.assertEqual(detect_text_layout(['KPM_01 PART 1: INTRO', 'KPM_02 PART 2: 10:30 MIX',
+                                             'KPM_03 PART 3: OUTRO', 'KPM_04 10:30']), "dateiname_dauer")
+
+    def test_only_durations_are_not_alternating(self):
+        self.assertEqual(detect_text_layout(['1:30', '2:15', '3:00', '0:45']), "dateiname_dauer")
+
+    def test_swapped_and_missing_durations(self):
+        self.assertEqual(detect_text_layout(['1:30;KPM_01_TITLE_a.wav', '2:15;KPM_02_TITLE_b.wav']), "vertauscht")
+        self.assertEqual(detect_text_layout(['KPM_01_TITLE_a.wav;Jazz', 'KPM_02_TITLE_b.wav;Pop']), "keine_dauern")
+
+class EnsureExtensionTest(unittest.TestCase):
+    def test_missing_or_foreign_extension_is_appended(self):
+        self.assertEqual(ensure_extension('/tmp/liste', ['.csv']), '/tmp/liste.csv')
+        self.assertEqual(ensure_extension('/tmp/liste.v2', ['.csv']), '/tmp/liste.v2.csv')
+
+    def test_matching_extension_is_kept(self):
+        self.assertEqual(ensure_extension('/tmp/liste.CSV', ['.csv']), '/tmp/liste.CSV')
+        self.assertEqual(ensure_extension('/tmp/fehler.txt', ['.csv', '.txt']), '/tmp/fehler.txt')
+
+@mock.patch('processing.log_error')
+class FilenameDurationTest(unittest.TestCase):
+    OPTIONS = ParseOptions(duration_patterns=[r'_(?P<min>\d+)m(?P<sec>\d+)s$', r'_\[(?P<min>\d+)-(?P<sec>\d+)\]$'])
+
+    def test_duration_is_stripped_before_tokenizing(self, _log):
+        self.assertEqual(parse_track_filename('KPM1234_05_SUMMER_RAIN_john_smith_3m45s.mp3', self.OPTIONS),
+                         ('kpm1234_05', 'summer rain', 'john smith'))
+
+    def test_text_file_without_durations(self, _log):
+        result = parse_text_file(fixture('filename_durations.txt'), {}, self.OPTIONS)
+        self.assertEqual(result.errors, [])
+        self.assertEqual([(t.title, format_duration(d)) for t, d in result.tracks.items()],
+                         [('summer rain', '3:45'), ('night drive', '2:15'), ('dawn', '0:30')])
+
+    def test_off_by_default(self, _log):
+        result = parse_text_file(fixture('filename_durations.txt'), {})
+        self.assertEqual([e["typ"] for e in result.errors], ["kein_semikolon", "ungueltige_dauer"])
+
+class HotfolderTest(unittest.TestCase):
+    def test_file_is_ready_once_unchanged(self):
+        with tempfile.TemporaryDirectory() as tmp:
+            path = os.path.join(tmp, 'liste.txt')
+            with open(path, 'w', encoding='utf-8') as f:
+                f.write('KPM1234_05_SUMMER_RAIN_john.wav;1:30\n')
+            state = {}
+            self.assertEqual(poll_hotfolder(tmp, state), [])
+            self.assertEqual(poll_hotfolder(tmp, state), [path])
+            self.assertEqual(poll_hotfolder(tmp, state), [])
+            with open(path, 'a', encoding='utf-8') as f:
+                f.write('JCM042_01_NIGHT_DRIVE_band.wav;2:15\n')
+            self.assertEqual(poll_hotfolder(tmp, state), [])
+            self.assertEqual(poll_hotfolder(tmp, state), [path])
+
+class CanonicalPathTest(unittest.TestCase):
+    def test_equivalent_spellings(self):
+        path = canonical_path(fixture('simple.txt'))
+        spellings = [os.path.join(FIXTURES, '.', 'simple.txt'),
+                     os.path.join(FIXTURES, '..', 'fixtures', 'simple.txt'),
+                     os.path.relpath(fixture('simple.txt')),
+                     FIXTURES + os.sep + os.sep + 'simple.txt']
+        for spelling in spellings:
+            self.assertEqual(canonical_path(spelling), path)
+
+    @unittest.skipUnless(hasattr(os, 'symlink'), "keine Symlinks")
+    def test_symlink(self):
+        with tempfile.TemporaryDirectory() as tmp:
+            link = os.path.join(tmp, 'link.txt')
+            try:
+                os.symlink(fixture('simple.txt'), link)
+            except OSError:
+                self.skipTest("Symlink nicht erlaubt")
+            self.assertEqual(canonical_path(link), canonical_path(fixture('simple.txt')))
+
+    def test_missing_file_keeps_raw_string(self):
+        self.assertEqual(canonical_path('./gibt/es/nicht.txt'), './gibt/es/nicht.txt')
+
+    def test_unc_path_not_mangled(self):
+        unc = '\\\\server\\share\\gema\\liste.txt'
+        self.assertEqual(canonical_path(unc), unc)
+        self.assertEqual(long_path(unc, windows=True), unc)
+
+    def test_long_windows_paths_get_prefix(self):
+        folder = '\\'.join(['ordner'] * 40)
+        unc = '\\\\server\\share\\' + folder + '\\liste.txt'
+        local = 'D:\\' + folder + '\\liste.txt'
+        self.assertEqual(long_path(unc, windows=True), '\\\\?\\UNC\\server\\share\\' + folder + '\\liste.txt')
+        self.assertEqual(long_path(local, windows=True), '\\\\?\\' + local)
+        self.assertEqual(long_path(long_path(unc, windows=True), windows=True), long_path(unc, windows=True))
+        self.assertEqual(plain_path(long_path(unc, windows=True)), unc)
+        self.assertEqual(plain_path(long_path(local, windows=True)), local)
+        self.assertEqual(long_path('/srv/' + folder.replace('\\', '/'), windows=False),
+                         '/srv/' + folder.replace('\\', '/'))
+
+@mock.patch('processing.log_error')
+@mock.patch('processing.STREAM_THRESHOLD', 0)
+class StreamingTest(unittest.TestCase):
+    def setUp(self):
+        self.label_dict = load_labelcodes(fixture('labelcodes.txt'))
+
+    def test_same_result_as_reading_whole_file(self, _log):
+        for name in ['simple.txt', 'delimited.txt', 'invalid_lines.txt']:
+            streamed = parse_text_file(fixture(name), self.label_dict)
+            with mock.patch('processing.STREAM_THRESHOLD', 1 << 30):
+                whole = parse_text_file(fixture(name), self.label_dict)
+            self.assertEqual(streamed, whole)
+
+    def test_format_check_uses_sample(self, _log):
+        with mock.patch('processing.FORMAT_SAMPLE_SIZE', 2):
+            result = parse_text_file(fixture('duration_first.txt'), {})
+        self.assertEqual(result.format_problem, "vertauscht")
+
+class DescribeTextFileTest(unittest.TestCase):
+    def test_fixtures(self):
+        self.assertEqual(describe_text_file(fixture('simple.txt')), "ASCII, LF")
+        self.assertEqual(describe_text_file(fixture('crlf.txt')), "ASCII, CRLF")
+        self.assertEqual(describe_text_file(fixture('bom.txt')).split(",")[0], "UTF-8 BOM")
+
+    def test_windows_1252_and_mixed_endings(self):
+        with tempfile.TemporaryDirectory() as tmp:
+            path = os.path.join(tmp, 'cp1252.txt')
+            with open(path, 'wb') as f:
+                f.write("KÜNSTLER.wav;1:00\r\nB.wav;2:00\n".encode('cp1252'))
+            self.assertEqual(describe_text_file(path), "kein UTF-8, gemischt (CRLF/LF)")
+
+    def test_missing_file(self):
+        self.assertEqual(describe_text_file(fixture('gibt_es_nicht.txt')), "")
+
+class FileLoggingTest(unittest.TestCase):
+    def setUp(self):
+        self.tmp = tempfile.TemporaryDirectory()
+        self.addCleanup(self.tmp.cleanup)
+        self.addCleanup(logging_utils.configure_file_logging, "aus")
+
+    def read_log(self):
+        with open(os.path.join(self.tmp.name, logging_utils.LOG_FILE_NAME), encoding='utf-8') as f:
+            return f.read()
+
+    def test_level_filters_messages(self):
+        logging_utils.configure_file_logging("INFO", self.tmp.name)
+        logging_utils.log_info("info-meldung")
+        logging_utils.log_debug("debug-meldung")
+        self.assertIn("info-meldung", self.read_log())
+        self.assertNotIn("debug-meldung", self.read_log())
+
+    def test_off_writes_nothing(self):
+        self.assertIsNone(logging_utils.configure_file_logging("aus", self.tmp.name))
+        logging_utils.log_info("info-meldung")
+        self.assertEqual(os.listdir(self.tmp.name), [])
+
+@mock.patch('sys.stderr')
+class GuiUnavailableTest(unittest.TestCase):
+    @mock.patch('main.run_without_gui', return_value=0)
+    def test_exports_given_files(self, run, _stderr):
+        with self.assertRaises(SystemExit) as raised:
+            main.gui_unavailable("kein Display gefunden", ['liste.txt'], {"default_output_dir": "/tmp/export"}, True)
+        self.assertEqual(raised.exception.code, 0)
+        run.assert_called_once_with(['liste.txt'], {"default_output_dir": "/tmp/export"}, True)
+
+    @mock.patch('main.run_without_gui')
+    def test_usage_without_files(self, run, _stderr):
+        with self.assertRaises(SystemExit) as raised:
+            main.gui_unavailable("kein Display gefunden", [])
+        self.assertEqual(raised.exception.code, 1)
+        run.assert_not_called()
+
+class ResolvePathsTest(unittest.TestCase):
+    def test_precedence(self):
+        config = {"labelcodes_file": "config.txt", "default_output_dir": "config_out"}
+        toml_config = {"labelcodes_file": "toml.txt", "default_output_dir": "toml_out"}
+        environ = {"GEMA_LABELCODES": "env.txt", "GEMA_EXPORT_DIR": "env_out"}
+        self.assertEqual(resolve_paths(config, {"labelcodes_file": "cli.txt"}, environ, toml_config),
+                         {"labelcodes_file": "cli.txt", "default_output_dir": "env_out"})
+        self.assertEqual(resolve_paths(config, None, {}, toml_config),
+                         {"labelcodes_file": "toml.txt", "default_output_dir": "toml_out"})
+        self.assertEqual(resolve_paths(config, None, {}, {}),
+                         {"labelcodes_file": "config.txt", "default_output_dir": "config_out"})
+        self.assertEqual(resolve_paths({}, None, {}, {}),
+                         {"labelcodes_file": "Labelcodes.txt", "default_output_dir": "."})
+
+    def test_source_is_logged(self):
+        with tempfile.TemporaryDirectory() as tmp:
+            self.addCleanup(logging_utils.configure_file_logging, "aus")
+            logging_utils.configure_file_logging("INFO", tmp)
+            resolve_paths({}, None, {"GEMA_EXPORT_DIR": "env_out"}, {})
+            with open(os.path.join(tmp, logging_utils.LOG_FILE_NAME), encoding='utf-8') as f:
+                log = f.read()
+            logging_utils.configure_file_logging("aus")
+        self.assertIn("default_output_dir = env_out (Quelle: GEMA_EXPORT_DIR)", log)
+        self.assertIn("labelcodes_file = Labelcodes.txt (Quelle: Standard)", log)
+
+    def test_source_is_reported(self):
+        messages = []
+        resolve_paths({}, {"labelcodes_file": "cli.txt"}, {}, {}, report=messages.append)
+        self.assertEqual(messages, ["labelcodes_file = cli.txt (Quelle: Kommandozeile)",
+                                    "default_output_dir = . (Quelle: Standard)"])
+
+    def test_toml_file(self):
+        with tempfile.TemporaryDirectory() as tmp:
+            path = os.path.join(tmp, 'gema.toml')
+            with open(path, 'w', encoding='utf-8') as f:
+                f.write('labelcodes_file = "/srv/gema/Labelcodes.txt"\n')
+            self.assertEqual(load_toml_config(path), {"labelcodes_file": "/srv/gema/Labelcodes.txt"})
+            self.assertEqual(load_toml_config(os.path.join(tmp, 'fehlt.toml')), {})
+
+class DefaultSettingsTest(unittest.TestCase):
+    def test_reset_keeps_language_and_recent_paths(self):
+        config = dict(DEFAULT_CONFIG, language="en", recent_paths=["a.txt"], csv_quoting="alle",
+                      field_replacements=[["x", "y"]])
+        settings = default_settings(config)
+        self.assertEqual(settings, dict(DEFAULT_CONFIG, language="en", recent_paths=["a.txt"]))
+        settings["csv_columns"].append("Jahr")
+        self.assertNotIn("Jahr", DEFAULT_CONFIG["csv_columns"])
+
+    def test_reset_keeps_labelcode_files_and_export_dir(self):
+        paths = {"labelcodes_file": "/srv/gema/Labelcodes.txt", "extra_labelcodes_files": ["/srv/gema/Extra.txt"],
+                 "default_output_dir": "/srv/gema/export"}
+        settings = default_settings(dict(DEFAULT_CONFIG, merge_durations=False, **paths))
+        self.assertEqual(settings, dict(DEFAULT_CONFIG, **paths))
+
+class ExpandPatternTest(unittest.TestCase):
+    def test_glob_matches_txt_files(self):
+        self.assertEqual(expand_pattern(fixture('s*.txt')), [fixture('simple.txt')])
+
+    def test_no_match(self):
+        self.assertEqual(expand_pattern(fixture('*.mp3')), [])
+
+    def test_scan_folder_counts_other_files(self):
+        with tempfile.TemporaryDirectory() as tmp:
+            os.mkdir(os.path.join(tmp, 'sub'))
+            for name in ['a.txt', 'B.TXT', 'notes.md', os.path.join('sub', 'c.txt'), os.path.join('sub', 'd.wav')]:
+                open(os.path.join(tmp, name), 'w').close()
+            files, ignored = scan_folder(tmp)
+        self.assertEqual(sorted(os.path.basename(f) for f in files), ['B.TXT', 'a.txt', 'c.txt'])
+        self.assertEqual(ignored, 2)
+
+@mock.patch('processing.log_error')
+class CueSheetTest(unittest.TestCase):
+    def copy_with_audio(self, tmp, seconds):
+        shutil.copy(fixture('sample.cue'), tmp)
+        with wave.open(os.path.join(tmp, 'album.wav'), 'wb') as audio:
+            audio.setnchannels(1)
+            audio.setsampwidth(1)
+            audio.setframerate(100)
+            audio.writeframes(b'\x80' * (seconds * 100))
+        return os.path.join(tmp, 'sample.cue')
+
+    def test_durations_from_index_and_file_length(self, _log):
+        with tempfile.TemporaryDirectory() as tmp:
+            result = parse_text_file(self.copy_with_audio(tmp, 390), {})
+        tracks = [(t.index, t.title, t.artist, t.filename, d) for t, d in result.tracks.items()]
+        self.assertEqual(tracks, [('01', 'night drive', 'the band', 'album.wav', 3.30),
+                                  ('02', 'summer rain', 'john smith', 'album.wav', 1.30),
+                                  ('03', 'last call', 'the band', 'album.wav', 1.30)])
+
+    def test_index_after_end_of_audio_file(self, _log):
+        with tempfile.TemporaryDirectory() as tmp:
+            result = parse_text_file(self.copy_with_audio(tmp, 240), {})
+        self.assertEqual(len(result.tracks), 2)
+        self.assertEqual(result.errors, [{"zeile": 12, "typ": "cue_laenge", "inhalt": "TRACK 03 (INDEX 5:00, album.wav 4:00)"}])
+        with tempfile.TemporaryDirectory() as tmp:
+            result = parse_text_file(self.copy_with_audio(tmp, 200), {})
+        # Die Dauer von TRACK 02 ergibt sich weiter aus den INDEX-Abständen, der Widerspruch wird aber gemeldet
+        self.assertEqual(list(result.tracks.values()), [3.30, 1.30])
+        self.assertEqual([e["inhalt"] for e in result.errors], ["TRACK 02 (INDEX 3:30, album.wav 3:20)",
+                                                                 "TRACK 03 (INDEX 5:00, album.wav 3:20)"])
+
+    def test_last_track_without_audio_file(self, _log):
+        result = parse_cue_file(fixture('sample.cue'), {})
+        self.assertEqual(len(result.tracks), 2)
+        self.assertEqual(result.errors, [{"zeile": 12, "typ": "ungueltige_dauer", "inhalt": "TRACK 03"}])
+        result = parse_cue_file(fixture('sample.cue'), {}, ParseOptions(default_duration="2:00"))
+        self.assertEqual(len(result.placeholder_tracks), 1)
+
+    def test_last_track_in_unsupported_audio_format(self, log):
+        with tempfile.TemporaryDirectory() as tmp:
+            with open(fixture('sample.cue'), encoding='utf-8') as f:
+                cue = f.read().replace('"album.wav" WAVE', '"album.mp3" MP3')
+            with open(os.path.join(tmp, 'sample.cue'), 'w', encoding='utf-8') as f:
+                f.write(cue)
+            open(os.path.join(tmp, 'album.mp3'), 'wb').close()
+            result = parse_text_file(os.path.join(tmp, 'sample.cue'), {})
+        self.assertEqual(len(result.tracks), 2)
+        self.assertEqual(result.errors, [{"zeile": 12, "typ": "audioformat", "inhalt": "TRACK 03 (album.mp3)"}])
+        self.assertTrue(any('"mp3"' in call.args[0] for call in log.call_args_list))
+
+    def test_found_in_folder(self, _log):
+        with tempfile.TemporaryDirectory() as tmp:
+            self.copy_with_audio(tmp, 1)
+            files, ignored = scan_folder(tmp)
+        self.assertEqual([os.path.basename(f) for f in files], ['sample.cue'])
+        self.assertEqual(ignored, 1)
+
+@mock.patch('processing.log_error')
+class AudioLengthTest(unittest.TestCase):
+    def test_reader_by_lowercase_extension(self, _log):
+        with mock.patch.dict(AUDIO_LENGTH_READERS, {"flac": lambda path: 12.5}):
+            self.assertEqual(audio_length('/musik/Track.FLAC'), 12.5)
+
+    def test_corrupt_file_is_logged(self, log):
+        with tempfile.TemporaryDirectory() as tmp:
+            path = os.path.join(tmp, 'kaputt.wav')
+            with open(path, 'wb') as f:
+                f.write(b'keine Wave-Datei')
+            self.assertIsNone(audio_length(path))
+            self.assertIsNone(audio_length(os.path.join(tmp, 'fehlt.wav')))
+        self.assertEqual(log.call_count, 2)
+        self.assertIn('kaputt.wav', log.call_args_list[0].args[0])
+
+    def test_unsupported_format_is_logged(self, log):
+        self.assertIsNone(audio_length('/musik/track.ogg'))
+        self.assertIn('"ogg"', log.call_args.args[0])
+
+class BulkReplaceTest(unittest.TestCase):
+    ROWS = [{"filename": "a.wav", "raw_index": "KPM1_01", "artist": "the beatlez"},
+            {"filename": "b.wav", "raw_index": "KPM1_02", "artist": "Beatlez Tribute"},
+            {"filename": "c.wav", "raw_index": "KPM1_03", "artist": "beatlezz"}]
+
+    def test_options(self):
+        self.assertEqual(bulk_replace(self.ROWS, "artist", "beatlez", "beatles"),
+                         [("a.wav", "the beatlez", "the beatles"), ("b.wav", "Beatlez Tribute", "beatles Tribute"),
+                          ("c.wav", "beatlezz", "beatlesz")])
+        self.assertEqual([c[0] for c in bulk_replace(self.ROWS, "artist", "beatlez", "beatles", whole_word=True)],
+                         ["a.wav", "b.wav"])
+        self.assertEqual([c[0] for c in bulk_replace(self.ROWS, "artist", "beatlez", "beatles", case_sensitive=True)],
+                         ["a.wav", "c.wav"])
+        self.assertEqual(bulk_replace(self.ROWS, "artist", "", "x"), [])
+
+    def test_index_override(self):
+        change = bulk_replace(self.ROWS, "index", "KPM1", "KPM2")[0]
+        self.assertEqual(change, ("a.wav", "KPM1_01", "KPM2_01"))
+        track = parse_track_info('KPM1_01_SUMMER_RAIN_john.wav', {'kpm2': '2061'},
+                                 ParseOptions(overrides={'KPM1_01_SUMMER_RAIN_john.wav': {"index": "KPM2_01"}}))
+        self.assertEqual((track.index, track.raw_index, track.label_code), ('kpm2_01', 'KPM2_01', '2061'))
+
+@mock.patch('processing.log_error')
+class LeadingZerosTest(unittest.TestCase):
+    def test_kept_separate_by_default(self, _log):
+        result = parse_text_file(fixture('padded_index.txt'), {})
+        self.assertEqual(len(result.tracks), 2)
+        self.assertEqual(result.collapsed, [])
+
+    def test_collapsed_when_enabled(self, _log):
+        options = ParseOptions(ignore_index_leading_zeros=True, merge_durations=True)
+        result = parse_text_file(fixture('padded_index.txt'), {}, options)
+        self.assertEqual(len(result.tracks), 1)
+        track = list(result.tracks)[0]
+        self.assertEqual(track.index, 'kpm1234_5')
+        self.assertEqual(track.raw_index, 'KPM1234_005')
+        self.assertAlmostEqual(result.tracks[track], 3.0)
+        self.assertEqual(result.collapsed, [('KPM1234_5_SUMMER_RAIN_john_smith.wav',
+                                             'KPM1234_005_SUMMER_RAIN_john_smith.wav')])
+
+class IndexNumbersTest(unittest.TestCase):
+    def formatted(self, index, mode):
+        return format_index_numbers(index, ParseOptions(index_numbers=mode, index_number_width=3))
+
+    def test_strip_leading_zeros(self):
+        self.assertEqual([self.formatted(i, "ohne_nullen") for i in ['001', 'cd01', '12a']], ['1', 'cd1', '12a'])
+
+    def test_pad_to_width(self):
+        self.assertEqual([self.formatted(i, "auffuellen") for i in ['001', 'cd01', '12a']], ['001', 'cd001', '012a'])
+
+    def test_unchanged_by_default(self):
+        self.assertEqual(format_index_numbers('cd01', ParseOptions()), 'cd01')
+
+    def test_track_number_from_index(self):
+        track = parse_track_info('KPM1234_005_SUMMER_RAIN_john.wav', {})
+        self.assertEqual((track.raw_index, track.track_number), ('KPM1234_005', 5))
+        self.assertEqual(column_value("Tracknummer", track, 1.3, 1), "5")
+        self.assertIsNone(parse_track_info('SUMMER_RAIN_john.wav', {}).track_number)
+
+class RenameTest(unittest.TestCase):
+    TEMPLATE = "{index}_{titel}_{kuenstler}.{ext}"
+
+    def setUp(self):
+        self.tmp = tempfile.TemporaryDirectory()
+        self.addCleanup(self.tmp.cleanup)
+        self.txt = os.path.join(self.tmp.name, 'list.txt')
+        with open(self.txt, 'w', encoding='utf-8') as f:
+            f.write("KPM1234_05_SUMMER_RAIN_John_Smith.wav;1:30\n"
+                    "KPM1234_05_SUMMER_RAIN_john_SMITH.wav;1:30\n"
+                    "JCM042_01_NIGHT_DRIVE_the_band.wav;2:15\n")
+        for name in ["KPM1234_05_SUMMER_RAIN_John_Smith.wav", "KPM1234_05_SUMMER_RAIN_john_SMITH.wav"]:
+            open(os.path.join(self.tmp.name, name), 'w').close()
+
+    def test_dry_run_reports_problems(self):
+        plan = plan_renames(self.txt, {}, self.TEMPLATE)
+        problems = [(os.path.basename(new), problem) for _, new, problem in plan]
+        self.assertEqual(problems, [("KPM1234_05_summer_rain_john_smith.wav", None),
+                                    ("KPM1234_05_summer_rain_john_smith.wav", "doppelter_name"),
+                                    ("JCM042_01_night_drive_the_band.wav", "nicht_gefunden")])
+        self.assertEqual(len(os.listdir(self.tmp.name)), 3)
+
+    def test_apply_renames_and_updates_text_file(self):
+        plan = plan_renames(self.txt, {}, self.TEMPLATE)
+        self.assertEqual(apply_renames(self.txt, plan), 1)
+        self.assertTrue(os.path.exists(os.path.join(self.tmp.name, "KPM1234_05_summer_rain_john_smith.wav")))
+        with open(self.txt, encoding='utf-8') as f:
+            self.assertEqual(f.readline(), "KPM1234_05_summer_rain_john_smith.wav;1:30\n")
+
+class ProcessSingleFileTest(unittest.TestCase):
+    def setUp(self):
+        self.tmp = tempfile.TemporaryDirectory()
+        self.addCleanup(self.tmp.cleanup)
+        cwd = os.getcwd()
+        os.chdir(self.tmp.name)  # error.log landet im Temp-Ordner
+        self.addCleanup(os.chdir, cwd)
+
+    def test_writes_csv(self):
+        label_dict = load_labelcodes(fixture('labelcodes.txt'))
+        process_single_file(fixture('simple.txt'), self.tmp.name, label_dict,
+                            ["Index", "Titel", "Künstler", "Labelcode", "Dauer"],
+                            options=ParseOptions(merge_durations=True))
+        with open(os.path.join(self.tmp.name, 'output_simple.csv'), encoding='utf-8') as f:
+            lines = f.read().splitlines()
+        self.assertEqual(lines, ["Index;Titel;Künstler;Labelcode;Dauer",
+                                 "KPM1234_05;summer rain;john smith;2061;2:15",
+                                 "JCM042_01;night drive;the band;48897;2:15"])
+
+    def test_strict_mode_refuses_export(self):
+        output = os.path.join(self.tmp.name, 'output_simple.csv')
+        summary, errors = process_single_file(fixture('simple.txt'), self.tmp.name, {}, ["Index", "Labelcode"],
+                                              strict=True, required_fields=["Index", "Labelcode"])
+        self.assertFalse(os.path.exists(output))
+        self.assertIn("3 Problem(e)", summary)
+        self.assertEqual([e["typ"] for e in errors], ["datei", "pflichtfeld", "pflichtfeld", "pflichtfeld"])
+        self.assertEqual(errors[1]["inhalt"], "KPM1234_05: Pflichtfeld leer: Labelcode")
+        label_dict = load_labelcodes(fixture('labelcodes.txt'))
+        process_single_file(fixture('simple.txt'), self.tmp.name, label_dict, ["Index", "Labelcode"],
+                            strict=True, required_fields=["Index", "Labelcode"])
+        self.assertTrue(os.path.exists(output))
+
+    @mock.patch('processing.PROGRESS_STEP', 1)
+    def test_cancel_removes_partial_output(self):
+        progress = []
+
+        def cancel_after_first_row():
+            return len(progress) >= 1
+        with self.assertRaises(ExportCancelled):
+            process_single_file(fixture('simple.txt'), self.tmp.name, {}, ["Index"],
+                                progress=lambda written, total: progress.append((written, total)),
+                                cancelled=cancel_after_first_row)
+        self.assertEqual(progress, [(1, 3)])
+        self.assertFalse(os.path.exists(os.path.join(self.tmp.name, 'output_simple.csv')))
+        csv_file = os.path.join(self.tmp.name, 'sammel.csv')
+        process_single_file(fixture('simple.txt'), self.tmp.name, {}, ["Index"], append_file=csv_file)
+        size = os.path.getsize(csv_file)
+        with self.assertRaises(ExportCancelled):
+            process_single_file(fixture('crlf.txt'), self.tmp.name, {}, ["Index"], append_file=csv_file,
+                                cancelled=lambda: True)
+        self.assertEqual(os.path.getsize(csv_file), size)
+
+    def test_normalize_case(self):
+        process_single_file(fixture('crlf.txt'), self.tmp.name, {}, ["Titel", "Künstler"],
+                            options=ParseOptions(normalize_case=True))
+        with open(os.path.join(self.tmp.name, 'output_crlf.csv'), encoding='utf-8') as f:
+            lines = f.read().splitlines()
+        self.assertEqual(lines[1], "Summer Rain;John Smith")
+
+    def test_position_column_continues_when_appending(self):
+        csv_file = os.path.join(self.tmp.name, 'sammel.csv')
+        for name in ['simple.txt', 'crlf.txt']:
+            process_single_file(fixture(name), self.tmp.name, {}, ["Position", "Index"], append_file=csv_file)
+        with open(csv_file, encoding='utf-8') as f:
+            lines = f.read().splitlines()
+        self.assertEqual(lines, ["Position;Index", "1;KPM1234_05", "2;JCM042_01", "3;KPM1234_05", "4;KPM1234_05",
+                                 "5;JCM042_01"])
+
+    def test_submission_id_column(self):
+        process_single_file(fixture('simple.txt'), self.tmp.name, {}, ["ID", "Index"],
+                            submission_id={"prefix": "SUB-", "width": 4, "start": 9})
+        with open(os.path.join(self.tmp.name, 'output_simple.csv'), encoding='utf-8') as f:
+            lines = f.read().splitlines()
+        self.assertEqual(lines, ["ID;Index", "SUB-0009;KPM1234_05", "SUB-0010;JCM042_01", "SUB-0011;KPM1234_05"])
+
+    def test_text_without_input_file(self):
+        process_single_file('zwischenablage', self.tmp.name, {}, ["Index", "Dauer"],
+                            text="KPM1234_05_SUMMER_RAIN_john.wav;1:30\nJCM042_01_NIGHT_DRIVE_the_band.wav;2:15\n")
+        # Keine Zwischendatei mit der Trackliste, nur die Ausgabe (und das Fehlerprotokoll)
+        self.assertEqual(sorted(os.listdir(self.tmp.name)), ['error.log', 'output_zwischenablage.csv'])
+        with open(os.path.join(self.tmp.name, 'output_zwischenablage.csv'), encoding='utf-8') as f:
+            self.assertEqual(f.read().splitlines(), ["Index;Dauer", "KPM1234_05;1:30", "JCM042_01;2:15"])
+
+    def test_report_total_in_real_seconds(self):
+        process_single_file(fixture('simple.txt'), self.tmp.name, {}, ["Index"], write_report=True)
+        with open(os.path.join(self.tmp.name, 'output_simple_report.json'), encoding='utf-8') as f:
+            report = json.load(f)
+        self.assertEqual(report["gesamtdauer_sekunden"], 270)
+        self.assertEqual(report["gesamtdauer"], "4:30")
+
+    def test_duration_and_seconds_columns(self):
+        for separator, expected in [(",", "1:30;90;1,50"), (".", "1:30;90;1.50")]:
+            process_single_file(fixture('simple.txt'), self.tmp.name, {}, ["Dauer", "Sekunden", "Minuten"],
+                                decimal_separator=separator)
+            with open(os.path.join(self.tmp.name, 'output_simple.csv'), encoding='utf-8') as f:
+                self.assertEqual(f.read().splitlines()[1], expected)
+
+    def test_seconds_column_is_real_seconds(self):
+        track = TrackInfo('kpm1', 'a', 'x')
+        self.assertEqual(column_value("Sekunden", track, parse_duration('2:30'), 1), "150")
+        self.assertEqual(column_value("Sekunden", track, parse_duration('4:15'), 1), "255")
+        self.assertEqual(column_value("Minuten", track, parse_duration('2:15'), 1, decimal_separator=","), "2,25")
+
+    def test_custom_headers(self):
+        process_single_file(fixture('crlf.txt'), self.tmp.name, {}, ["Index", "Künstler"],
+                            csv_headers={"Künstler": "Interpret/Komponist"})
+        with open(os.path.join(self.tmp.name, 'output_crlf.csv'), encoding='utf-8') as f:
+            lines = f.read().splitlines()
+        self.assertEqual(lines[:2], ["Index;Interpret/Komponist", "KPM1234_05;john smith"])
+
+    def test_fixed_width_file(self):
+        summary, _ = process_single_file(fixture('simple.txt'), self.tmp.name, {}, ["Index", "Titel", "Dauer"],
+                                         fixed_widths={"Index": 12, "Titel": 8, "Dauer": 6},
+                                         options=ParseOptions(merge_durations=True))
+        with open(os.path.join(self.tmp.name, 'output_simple_fest.txt'), encoding='utf-8', newline='') as f:
+            lines = f.read().split('\r\n')
+        self.assertEqual(lines, ["KPM1234_05  summer r2:15  ", "JCM042_01   night dr2:15  ", ""])
+        self.assertIn("Titel", summary)
+
+    def test_quote_all_fields(self):
+        process_single_file(fixture('crlf.txt'), self.tmp.name, {}, ["Index", "Titel"], csv_quoting="alle")
+        with open(os.path.join(self.tmp.name, 'output_crlf.csv'), encoding='utf-8') as f:
+            lines = f.read().splitlines()
+        self.assertEqual(lines[:2], ['"Index";"Titel"', '"KPM1234_05";"summer rain"'])
+
+    def test_no_quoting_escapes_delimiter(self):
+        options = ParseOptions(overrides={'KPM1234_05_SUMMER_RAIN_john_smith.wav': {"title": "rain; again"}})
+        for quoting, expected in [("minimal", 'KPM1234_05;"rain; again"'), ("keine", 'KPM1234_05;rain\\; again')]:
+            process_single_file(fixture('crlf.txt'), self.tmp.name, {}, ["Index", "Titel"], options=options,
+                                csv_quoting=quoting)
+            with open(os.path.join(self.tmp.name, 'output_crlf.csv'), encoding='utf-8') as f:
+                self.assertEqual(f.read().splitlines()[1], expected)
+
+    def test_tsv_export_uses_tabs_and_extension(self):
+        process_single_file(fixture('crlf.txt'), self.tmp.name, {}, ["Index", "Titel"], csv_quoting="keine",
+                            export_format="tsv")
+        with open(os.path.join(self.tmp.name, 'output_crlf.tsv'), encoding='utf-8') as f:
+            self.assertEqual(f.read().splitlines()[:2], ['Index\tTitel', 'KPM1234_05\tsummer rain'])
+
+    def test_template_has_only_header(self):
+        for name, expected in [('vorlage.csv', 'Index;Titel\n'), ('vorlage.tsv', 'Index\tTitel\n')]:
+            path = os.path.join(self.tmp.name, name)
+            write_template(path, ["Index", "Titel"], "keine")
+            with open(path, encoding='utf-8') as f:
+                self.assertEqual(f.read(), expected)
+
+    @mock.patch('processing.log_error')
+    def test_no_tracks_is_reported(self, _log):
+        with open('leer.txt', 'w', encoding='utf-8') as f:
+            f.write("nur text ohne semikolon\n")
+        _, errors = process_single_file('leer.txt', self.tmp.name, {}, ["Index"])
+        self.assertEqual([e["typ"] for e in errors], ["kein_semikolon", "keine_tracks"])
+
+    def test_missing_label_codes_are_warnings(self):
+        _, errors = process_single_file(fixture('crlf.txt'), self.tmp.name, {'kpm': '2061'}, ["Index"])
+        self.assertEqual([(e["typ"], e["inhalt"]) for e in errors], [("kein_labelcode", "JCM042_01")])
+        self.assertEqual([error_severity(e) for e in errors], ["warnung"])
+        self.assertEqual(error_severity({"zeile": None, "typ": "datei", "inhalt": ""}), "fehler")
+
+    def test_missing_audio_files_are_warnings(self):
+        shutil.copy(fixture('crlf.txt'), 'liste.txt')
+        open('kpm1234_05_summer_rain_JOHN_SMITH.WAV', 'w').close()
+        options = ParseOptions(text_only_tracks="markieren")
+        _, errors = process_single_file('liste.txt', self.tmp.name, {'kpm': '2061', 'jcm': '1234'}, ["Index"],
+                                        options=options)
+        self.assertEqual([(e["typ"], e["inhalt"]) for e in errors],
+                         [("kein_audiofile", "JCM042_01_NIGHT_DRIVE_the_band.wav")])
+
+    def test_text_only_tracks_can_be_excluded(self):
+        shutil.copy(fixture('crlf.txt'), 'liste.txt')
+        open('KPM1234_05_SUMMER_RAIN_john_smith.wav', 'w').close()
+        summary, _ = process_single_file('liste.txt', self.tmp.name, {}, ["Index"],
+                                         options=ParseOptions(text_only_tracks="ausschliessen"))
+        with open(os.path.join(self.tmp.name, 'output_liste.csv'), encoding='utf-8') as f:
+            self.assertEqual(f.read().splitlines(), ['Index', 'KPM1234_05'])
+        self.assertIn("nicht exportiert): 1", summary)
+
+    def test_export_errors_includes_severity(self):
+        export_errors('fehler.csv', {'a.txt': [{"zeile": 3, "typ": "kein_semikolon", "inhalt": "x"},
+                                               {"zeile": None, "typ": "datei", "inhalt": "leer"}]})
+        with open('fehler.csv', encoding='utf-8') as f:
+            self.assertEqual(f.read().splitlines(), ["Datei;Zeile;Typ;Inhalt;Schwere",
+                                                     "a.txt;3;kein_semikolon;x;warnung",
+                                                     "a.txt;;datei;leer;fehler"])
+
+    def test_label_code_aggregate(self):
+        tracks = parse_text_file(fixture('crlf.txt'), {'kpm': '2061'}).tracks
+        aggregate = label_code_aggregate([tracks, tracks])
+        self.assertEqual({code: count for code, (count, _) in aggregate.items()}, {'2061': 2, '': 2})
+        self.assertEqual(aggregate['2061'][1], 180)
+        write_label_aggregate('summe.csv', aggregate)
+        with open('summe.csv', encoding='utf-8') as f:
+            self.assertEqual(f.read().splitlines(), ["Labelcode;Anzahl;Gesamtdauer", "2061;2;3:00",
+                                                     "ohne Labelcode;2;4:30"])
+
+    def test_label_code_aggregate_of_merged_durations(self):
+        # Über 100 Sekunden in einer Datei (3 x 0:59) und über 60 Sekunden in der anderen (0:45 + 0:30)
+        with open('a.txt', 'w', encoding='utf-8') as f:
+            f.write('KPM1234_05_SUMMER_RAIN_john.wav;0:59\n' * 3)
+        with open('b.txt', 'w', encoding='utf-8') as f:
+            f.write('KPM1234_06_NIGHT_DRIVE_john.wav;0:45\nKPM1234_07_LAST_CALL_john.wav;0:30\n')
+        options = ParseOptions(merge_durations=True)
+        aggregate = label_code_aggregate([parse_text_file(name, {'kpm': '2061'}, options).tracks
+                                          for name in ['a.txt', 'b.txt']])
+        self.assertEqual(aggregate, {'2061': (3, 252)})
+        write_label_aggregate('summe.csv', aggregate)
+        with open('summe.csv', encoding='utf-8') as f:
+            self.assertEqual(f.read().splitlines()[1], "2061;3;4:12")
+
+    def test_snapshot_and_compare(self):
+        snapshot = {}
+        process_single_file(fixture('crlf.txt'), self.tmp.name, {}, ["Index"], snapshot=snapshot)
+        old = snapshot[fixture('crlf.txt')]
+        self.assertEqual(list(old), ['KPM1234_05', 'JCM042_01'])
+        self.assertEqual(old['KPM1234_05'], {"title": "summer rain", "artist": "john smith", "label_code": "",
+                                             "duration": "1:30"})
+        new = dict(old, KPM1234_05=dict(old['KPM1234_05'], label_code='2061'), NEW_01={})
+        del new['JCM042_01']
+        self.assertEqual(compare_snapshots(old, new), [("geaendert", 'KPM1234_05', [("label_code", "", "2061")]),
+                                                       ("neu", 'NEW_01', []), ("entfernt", 'JCM042_01', [])])
+
+    def test_index_numbers_on_export(self):
+        process_single_file(fixture('crlf.txt'), self.tmp.name, {}, ["Index"],
+                            options=ParseOptions(index_numbers="ohne_nullen"))
+        with open(os.path.join(self.tmp.name, 'output_crlf.csv'), encoding='utf-8') as f:
+            self.assertEqual(f.read().splitlines()[1:], ["KPM1234_5", "JCM42_1"])
+
+    def test_filename_column(self):
+        process_single_file(fixture('crlf.txt'), self.tmp.name, {}, ["Index", "Dateiname"])
+        with open(os.path.join(self.tmp.name, 'output_crlf.csv'), encoding='utf-8') as f:
+            lines = f.read().splitlines()
+        self.assertEqual(lines[1], "KPM1234_05;KPM1234_05_SUMMER_RAIN_john_smith.wav")
+
+if __name__ == '__main__':
+    unittest.main()